@@ -0,0 +1,15 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use tdx_quote_parser::{parse_quote, QuoteRef};
+
+fn bench_parse(c: &mut Criterion) {
+    let data = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/quote.dat")).expect("quote.dat");
+
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.bench_function("parse_quote", |b| b.iter(|| parse_quote(black_box(&data)).unwrap()));
+    group.bench_function("QuoteRef::parse", |b| b.iter(|| QuoteRef::parse(black_box(&data)).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
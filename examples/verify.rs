@@ -0,0 +1,74 @@
+//! End-to-end verification of a quote against Intel PCS collateral.
+//!
+//! ```text
+//! cargo run --example verify --features collateral -- <quote> <tcb_info.json> <qe_identity.json>
+//! ```
+//!
+//! The TCB info and QE identity are the JSON bodies of the PCS `tdx/certification/v4/tcb`
+//! and `tdx/certification/v4/qe/identity` responses. Their signatures are not checked here.
+
+use std::process::ExitCode;
+
+use tdx_quote_parser::collateral::{QeIdentity, TcbInfo, TcbStatus};
+use tdx_quote_parser::{Quote, VerifyError, INTEL_SGX_ROOT_CA};
+
+fn check(name: &str, result: Result<(), VerifyError>) -> bool {
+    match result {
+        Ok(()) => println!("{:<24} ok", name),
+        Err(ref err) => println!("{:<24} FAILED: {}", name, err),
+    }
+    result.is_ok()
+}
+
+fn status(name: &str, result: Result<TcbStatus, VerifyError>) -> bool {
+    match result {
+        Ok(status) => {
+            println!("{:<24} {:?}", name, status);
+            status == TcbStatus::UpToDate
+        }
+        Err(err) => {
+            println!("{:<24} FAILED: {}", name, err);
+            false
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [quote, tcb_info, qe_identity] = args.as_slice() else {
+        eprintln!("usage: verify <quote> <tcb_info.json> <qe_identity.json>");
+        return ExitCode::from(2);
+    };
+    let load = || -> Result<(Quote, TcbInfo, QeIdentity), Box<dyn std::error::Error>> {
+        Ok((
+            Quote::try_from(std::fs::read(quote)?)?,
+            TcbInfo::from_json(&std::fs::read_to_string(tcb_info)?)?,
+            QeIdentity::from_json(&std::fs::read_to_string(qe_identity)?)?,
+        ))
+    };
+    let (quote, tcb_info, qe_identity) = match load() {
+        Ok(loaded) => loaded,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::from(2);
+        }
+    };
+
+    // Evaluate every step so the output shows all failures, not just the first.
+    let results = [
+        check("PCK certificate chain", quote.verify_cert_chain(INTEL_SGX_ROOT_CA)),
+        check("QE report signature", quote.verify_qe_report_signature()),
+        check("QE report binding", quote.verify_qe_report_binding()),
+        check("quote signature", quote.verify_signature()),
+        status("QE identity", quote.qe_identity_status(&qe_identity)),
+        status("platform TCB", quote.tcb_status(&tcb_info)),
+    ];
+
+    if results.iter().all(|ok| *ok) {
+        println!("\ntrust decision: TRUSTED");
+        ExitCode::SUCCESS
+    } else {
+        println!("\ntrust decision: NOT TRUSTED");
+        ExitCode::FAILURE
+    }
+}
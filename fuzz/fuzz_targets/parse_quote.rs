@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tdx_quote_parser::{parse_quote, QuoteRef};
+
+// Any input must be rejected with an error rather than a panic.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(quote) = parse_quote(data) {
+        let _ = quote.to_string();
+        let _ = quote.to_bytes();
+        let _ = quote.verify_signature();
+        let _ = quote.verify_qe_report_binding();
+        let _ = quote.certification_data().pck_cert_chain();
+    }
+    let _ = QuoteRef::parse(data);
+});
@@ -0,0 +1,59 @@
+/* C bindings of the `ffi` feature. Cargo.toml does not list a C crate type,
+ * which `no_std` builds without a panic handler could not link, so build the
+ * static library with
+ *
+ *     cargo rustc --lib --release --features ffi --crate-type staticlib
+ *
+ * or a shared library with `--crate-type cdylib`, and link
+ * target/release/libtdx_quote_parser.a or .so. */
+
+#ifndef TDX_QUOTE_PARSER_H
+#define TDX_QUOTE_PARSER_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define TDX_OK 0
+#define TDX_ERR_NULL_POINTER -1
+#define TDX_ERR_PARSE -2
+#define TDX_ERR_NOT_TDX -3
+
+typedef struct TdxQuoteView {
+    uint16_t version;
+    uint16_t attestation_key_type;
+    uint32_t tee_type;
+    uint16_t td_quote_body_type;
+    uint8_t tee_tcb_svn[16];
+    uint8_t mrseam[48];
+    uint8_t mrsignerseam[48];
+    uint8_t seamattributes[8];
+    uint8_t tdattributes[8];
+    uint8_t xfam[8];
+    uint8_t mrtd[48];
+    uint8_t mrconfigid[48];
+    uint8_t mrowner[48];
+    uint8_t mrownerconfig[48];
+    uint8_t rtmr0[48];
+    uint8_t rtmr1[48];
+    uint8_t rtmr2[48];
+    uint8_t rtmr3[48];
+    uint8_t reportdata[64];
+} TdxQuoteView;
+
+/* Parses `len` bytes at `data` into the caller-owned `*out`. Neither pointer
+ * is retained. Returns TDX_OK or a negative TDX_ERR_* code. */
+int tdx_parse_quote(const uint8_t *data, size_t len, TdxQuoteView *out);
+
+/* Message of the last failure on this thread, or NULL. Owned by the library;
+ * valid until the next failing call on the same thread. */
+const char *tdx_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,292 @@
+//! Decoders for the attribute bitfields carried in the report bodies.
+
+use core::fmt;
+
+/// Decoded view of the 8-byte `tdattributes` field.
+#[derive(Debug, Clone)]
+pub struct TdAttributes {
+    /// TUD bit 0: the TD runs in debug mode and is not trustworthy.
+    pub debug: bool,
+    /// TUD bits 1-7.
+    pub tud_reserved: u8,
+    /// SEC bits 8-27.
+    pub sec_reserved: u32,
+    /// SEC bit 28: EPT violations on pending pages are not converted to #VE.
+    pub septve_disable: bool,
+    /// SEC bit 29: the TD may be migrated.
+    pub migratable: bool,
+    /// SEC bit 30: the TD may use supervisor protection keys.
+    pub pks: bool,
+    /// SEC bit 31: the TD may use key locker.
+    pub kl: bool,
+    /// OTHER bits 32-62.
+    pub other_reserved: u32,
+    /// OTHER bit 63: the TD may use perfmon and PERF_METRICS.
+    pub perfmon: bool,
+}
+
+impl TdAttributes {
+    pub fn from_bytes(tdattributes: [u8; 8]) -> Self {
+        let bits = u64::from_le_bytes(tdattributes);
+        TdAttributes {
+            debug: bits & 1 != 0,
+            tud_reserved: ((bits >> 1) & 0x7f) as u8,
+            sec_reserved: ((bits >> 8) & 0xfffff) as u32,
+            septve_disable: (bits >> 28) & 1 != 0,
+            migratable: (bits >> 29) & 1 != 0,
+            pks: (bits >> 30) & 1 != 0,
+            kl: (bits >> 31) & 1 != 0,
+            other_reserved: ((bits >> 32) & 0x7fff_ffff) as u32,
+            perfmon: (bits >> 63) & 1 != 0,
+        }
+    }
+}
+
+impl fmt::Display for TdAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let debug = if self.debug { "True" } else { "False" };
+        writeln!(f, "TUD:\n\t   DEBUG: {}\n\t   RESERVED: {}", debug, self.tud_reserved)?;
+        // `migratable` is left out to keep the output of earlier versions.
+        writeln!(
+            f,
+            "\tSEC:\n\t  RESERVED: {}\n\t  SEPT_VE_DISABLE: {}\n\t  PKS: {}\n\t  KL: {}",
+            self.sec_reserved, self.septve_disable as u8, self.pks as u8, self.kl as u8
+        )?;
+        write!(f, "\tOTHER:\n\t  RESERVED: {}\n\t  PERFMON: {}", self.other_reserved, self.perfmon as u8)
+    }
+}
+
+/// Decoded view of the 8-byte `seamattributes` field.
+///
+/// The TDX module ABI through version 1.5 defines no SEAM attribute bits and
+/// requires the field to be zero, so every bit is reported as reserved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeamAttributes {
+    /// Bits 0-63.
+    pub reserved: u64,
+}
+
+impl SeamAttributes {
+    pub fn from_bytes(seamattributes: [u8; 8]) -> Self {
+        SeamAttributes { reserved: u64::from_le_bytes(seamattributes) }
+    }
+}
+
+impl fmt::Display for SeamAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RESERVED: {:#x}", self.reserved)
+    }
+}
+
+/// Decoded view of the 8-byte `xfam` field, one flag per XSTATE component.
+#[derive(Debug, Clone)]
+pub struct Xfam {
+    /// Bit 0: x87 FPU state.
+    pub x87: bool,
+    /// Bit 1: SSE state.
+    pub sse: bool,
+    /// Bit 2: AVX upper halves of the YMM registers.
+    pub avx: bool,
+    /// Bit 3: MPX bound registers.
+    pub mpx_bndregs: bool,
+    /// Bit 4: MPX bound configuration and status.
+    pub mpx_bndcsr: bool,
+    /// Bit 5: AVX-512 opmask registers.
+    pub avx512_opmask: bool,
+    /// Bit 6: AVX-512 upper halves of ZMM0-15.
+    pub avx512_zmm_hi256: bool,
+    /// Bit 7: AVX-512 ZMM16-31.
+    pub avx512_hi16_zmm: bool,
+    /// Bit 8: processor trace.
+    pub pt: bool,
+    /// Bit 9: protection key rights register.
+    pub pkru: bool,
+    /// Bit 10: PASID state.
+    pub pasid: bool,
+    /// Bit 11: CET user state.
+    pub cet_u: bool,
+    /// Bit 12: CET supervisor state.
+    pub cet_s: bool,
+    /// Bit 13: hardware duty cycling.
+    pub hdc: bool,
+    /// Bit 14: user interrupts.
+    pub uli: bool,
+    /// Bit 15: architectural last branch records.
+    pub lbr: bool,
+    /// Bit 16: hardware P-states.
+    pub hwp: bool,
+    /// Bit 17: AMX tile configuration.
+    pub amx_tilecfg: bool,
+    /// Bit 18: AMX tile data.
+    pub amx_tiledata: bool,
+}
+
+impl Xfam {
+    pub fn from_bytes(xfam: [u8; 8]) -> Self {
+        let bits = u64::from_le_bytes(xfam);
+        let bit = |n: u32| (bits >> n) & 1 != 0;
+        Xfam {
+            x87: bit(0),
+            sse: bit(1),
+            avx: bit(2),
+            mpx_bndregs: bit(3),
+            mpx_bndcsr: bit(4),
+            avx512_opmask: bit(5),
+            avx512_zmm_hi256: bit(6),
+            avx512_hi16_zmm: bit(7),
+            pt: bit(8),
+            pkru: bit(9),
+            pasid: bit(10),
+            cet_u: bit(11),
+            cet_s: bit(12),
+            hdc: bit(13),
+            uli: bit(14),
+            lbr: bit(15),
+            hwp: bit(16),
+            amx_tilecfg: bit(17),
+            amx_tiledata: bit(18),
+        }
+    }
+
+    /// Both MPX components are enabled.
+    pub fn mpx(&self) -> bool {
+        self.mpx_bndregs && self.mpx_bndcsr
+    }
+
+    /// All three AVX-512 components are enabled.
+    pub fn avx512(&self) -> bool {
+        self.avx512_opmask && self.avx512_zmm_hi256 && self.avx512_hi16_zmm
+    }
+
+    /// Both CET components are enabled.
+    pub fn cet(&self) -> bool {
+        self.cet_u && self.cet_s
+    }
+
+    /// Both AMX components are enabled.
+    pub fn amx(&self) -> bool {
+        self.amx_tilecfg && self.amx_tiledata
+    }
+}
+
+/// Named components of the 16-byte `tee_tcb_svn` field.
+///
+/// Only the first three bytes are defined by the TDX module; the rest are
+/// reserved and kept as-is so policies can still check them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TeeTcbSvn {
+    /// Byte 0: TDX module minor SVN.
+    pub tdx_module_minor_svn: u8,
+    /// Byte 1: TDX module major SVN.
+    pub tdx_module_major_svn: u8,
+    /// Byte 2: SVN of the last SEAM loader patch applied.
+    pub seam_last_patch_svn: u8,
+    /// Bytes 3-15.
+    pub reserved: [u8; 13],
+}
+
+impl TeeTcbSvn {
+    pub fn from_bytes(tee_tcb_svn: [u8; 16]) -> Self {
+        let mut reserved = [0u8; 13];
+        reserved.copy_from_slice(&tee_tcb_svn[3..]);
+        TeeTcbSvn {
+            tdx_module_minor_svn: tee_tcb_svn[0],
+            tdx_module_major_svn: tee_tcb_svn[1],
+            seam_last_patch_svn: tee_tcb_svn[2],
+            reserved,
+        }
+    }
+
+    /// The 16 bytes in their `tee_tcb_svn` order.
+    pub fn to_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[0] = self.tdx_module_minor_svn;
+        bytes[1] = self.tdx_module_major_svn;
+        bytes[2] = self.seam_last_patch_svn;
+        bytes[3..].copy_from_slice(&self.reserved);
+        bytes
+    }
+
+    /// Whether every component is at least the one in `min`.
+    ///
+    /// This is deliberately not an `Ord` impl: TCB levels are only partially
+    /// ordered, and a lexicographic comparison would let a higher value in one
+    /// component make up for a lower value in a later one.
+    pub fn meets_minimum(&self, min: &TeeTcbSvn) -> bool {
+        self.to_bytes().iter().zip(min.to_bytes()).all(|(svn, min)| *svn >= min)
+    }
+
+    /// The TDX module SVN as a single `(major, minor)`-ordered value, suitable
+    /// for comparing against a minimum.
+    pub fn tdx_module_svn(&self) -> u16 {
+        u16::from_be_bytes([self.tdx_module_major_svn, self.tdx_module_minor_svn])
+    }
+}
+
+/// Names of the 16 TEE TCB SVN components as used in Intel TCB info JSON.
+const TDX_TCB_COMPONENTS: [&str; 16] = [
+    "tdxtcbcomp01", "tdxtcbcomp02", "tdxtcbcomp03", "tdxtcbcomp04", "tdxtcbcomp05", "tdxtcbcomp06", "tdxtcbcomp07",
+    "tdxtcbcomp08", "tdxtcbcomp09", "tdxtcbcomp10", "tdxtcbcomp11", "tdxtcbcomp12", "tdxtcbcomp13", "tdxtcbcomp14",
+    "tdxtcbcomp15", "tdxtcbcomp16",
+];
+
+/// Pairs each byte of a 16-byte TEE TCB SVN with its TCB info component name.
+pub(crate) fn labeled_tcb_components(tee_tcb_svn: [u8; 16]) -> impl Iterator<Item = (&'static str, u8)> {
+    TDX_TCB_COMPONENTS.into_iter().zip(tee_tcb_svn)
+}
+
+/// Decoded view of the SGX report `misc_select` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MiscSelect {
+    /// Bit 0: the enclave reports page fault and #GP details in its SSA frame.
+    pub exinfo: bool,
+    /// Bits 1-31.
+    pub reserved: u32,
+}
+
+impl MiscSelect {
+    pub fn from_u32(misc_select: u32) -> Self {
+        MiscSelect { exinfo: misc_select & 1 != 0, reserved: misc_select >> 1 }
+    }
+}
+
+/// Decoded view of the 16-byte SGX report `attributes` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SgxAttributes {
+    /// Bit 0: the enclave has been initialized by EINIT.
+    pub init: bool,
+    /// Bit 1: the enclave runs in debug mode and is not trustworthy.
+    pub debug: bool,
+    /// Bit 2: the enclave runs in 64-bit mode.
+    pub mode64bit: bool,
+    /// Bit 4: the enclave may request the provisioning key.
+    pub provision_key: bool,
+    /// Bit 5: the enclave may request the EINIT token key.
+    pub einit_token_key: bool,
+    /// Bit 7: key separation and sharing is enabled.
+    pub kss: bool,
+    /// Remaining flag bits, with the named bits above cleared.
+    pub reserved: u64,
+    /// Bytes 8-15: the XSAVE feature request mask.
+    pub xfrm: u64,
+}
+
+impl SgxAttributes {
+    pub fn from_bytes(attributes: [u8; 16]) -> Self {
+        let mut flags = [0u8; 8];
+        let mut xfrm = [0u8; 8];
+        flags.copy_from_slice(&attributes[..8]);
+        xfrm.copy_from_slice(&attributes[8..]);
+        let flags = u64::from_le_bytes(flags);
+        SgxAttributes {
+            init: flags & 1 != 0,
+            debug: (flags >> 1) & 1 != 0,
+            mode64bit: (flags >> 2) & 1 != 0,
+            provision_key: (flags >> 4) & 1 != 0,
+            einit_token_key: (flags >> 5) & 1 != 0,
+            kss: (flags >> 7) & 1 != 0,
+            reserved: flags & !0b1011_0111,
+            xfrm: u64::from_le_bytes(xfrm),
+        }
+    }
+}
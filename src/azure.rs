@@ -0,0 +1,23 @@
+//! Adapter for quotes delivered through Azure confidential VM attestation.
+
+use base64::alphabet::URL_SAFE;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+
+use crate::{parse_quote, ParseError, Quote};
+
+/// base64url as used by Azure, accepting input with or without padding.
+const BASE64URL: GeneralPurpose =
+    GeneralPurpose::new(&URL_SAFE, GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent));
+
+/// Parses the TD quote out of an Azure attestation payload.
+///
+/// This is the shape returned by the IMDS `acc/tdquote` endpoint and sent to
+/// Microsoft Azure Attestation, `{"quote": "<base64url>", "runtimeData": {...}}`,
+/// where `runtimeData` is optional and ignored.
+pub fn parse_maa_response(json: &str) -> Result<Quote, ParseError> {
+    let response: serde_json::Value = serde_json::from_str(json).map_err(|_| ParseError::InvalidJsonEnvelope)?;
+    let encoded = response.get("quote").and_then(|quote| quote.as_str()).ok_or(ParseError::InvalidJsonEnvelope)?;
+    let data = BASE64URL.decode(encoded.trim())?;
+    parse_quote(&data)
+}
@@ -1,184 +1,14 @@
-use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::Cursor;
-use uuid::Uuid;
 use std::env;
 use std::fs::File;
 use std::io::Read;
-use hex;
+use std::process;
 
-#[derive(Debug)]
-enum TEEType {
-    SGX = 0x00000000,
-    TDX = 0x00000081,
-}
-
-#[derive(Debug)]
-struct QuoteHeader {
-    version: u16,
-    attestation_key_type: u16,
-    tee_type: TEEType,
-    reserved1: [u8; 2],
-    reserved2: [u8; 2],
-    qe_vendor_id: Uuid,
-    user_data: [u8; 20],
-}
-
-#[derive(Debug)]
-struct TDQuoteBody {
-    tee_tcb_svn: [u8; 16],
-    mrseam: [u8; 48],
-    mrsignerseam: [u8; 48],
-    seamattributes: [u8; 8],
-    tdattributes: [u8; 8],
-    xfam: [u8; 8],
-    mrtd: [u8; 48],
-    mrconfigid: [u8; 48],
-    mrowner: [u8; 48],
-    mrownerconfig: [u8; 48],
-    rtmr0: [u8; 48],
-    rtmr1: [u8; 48],
-    rtmr2: [u8; 48],
-    rtmr3: [u8; 48],
-    reportdata: [u8; 64],
-}
+use tdx_quote_parser::{parse_quote, ParseError};
 
-#[derive(Debug)]
-struct Quote {
-    header: QuoteHeader,
-    td_quote_body: TDQuoteBody,
-}
-
-fn parse_td_quote_body(cursor: &mut Cursor<&[u8]>) -> TDQuoteBody {
-    TDQuoteBody {
-        tee_tcb_svn: {
-            let mut buf = [0; 16];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        mrseam: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        mrsignerseam: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        seamattributes: {
-            let mut buf = [0; 8];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        tdattributes: {
-            let mut buf = [0; 8];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        xfam: {
-            let mut buf = [0; 8];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        mrtd: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        mrconfigid: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        mrowner: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        mrownerconfig: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        rtmr0: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        rtmr1: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        rtmr2: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        rtmr3: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-        reportdata: {
-            let mut buf = [0; 64];
-            cursor.read_exact(&mut buf).unwrap();
-            buf.reverse();
-            buf
-        },
-    }
-}
-
-fn parse_quote(data: &[u8]) -> Quote {
-    let mut cursor = Cursor::new(data);
-
-    let header = QuoteHeader {
-        version: cursor.read_u16::<LittleEndian>().unwrap(),
-        attestation_key_type: cursor.read_u16::<LittleEndian>().unwrap(),
-        tee_type: match cursor.read_u32::<LittleEndian>().unwrap() {
-            0x00000000 => TEEType::SGX,
-            0x00000081 => TEEType::TDX,
-            _ => panic!("Invalid TEE type"),
-        },
-        reserved1: {
-            let mut buf = [0; 2];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        reserved2: {
-            let mut buf = [0; 2];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        qe_vendor_id: {
-            let mut buf = [0; 16];
-            cursor.read_exact(&mut buf).unwrap();
-            Uuid::from_bytes(buf)
-        },
-        user_data: {
-            let mut buf = [0; 20];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-    };
-
-    let td_quote_body = parse_td_quote_body(&mut cursor);
-
-    Quote { header, td_quote_body }
+/// Hex of a field with its bytes reversed, as this tool has always shown them.
+fn reversed<const N: usize>(mut bytes: [u8; N]) -> String {
+    bytes.reverse();
+    hex::encode(bytes)
 }
 
 fn extract_tdattributes_info(tdattributes: [u8; 8]) -> String {
@@ -230,33 +60,49 @@ fn main() {
     }
 
     // Parse the quote
-    let quote = parse_quote(&file_contents);
+    let quote = match parse_quote(&file_contents) {
+        Ok(quote) if quote.header.version != 4 => {
+            eprintln!("Error parsing quote: {}", ParseError::UnsupportedVersion(quote.header.version));
+            process::exit(1);
+        }
+        Ok(quote) => quote,
+        Err(err) => {
+            eprintln!("Error parsing quote: {}", err);
+            process::exit(1);
+        }
+    };
+    let Some(body) = quote.body.td_quote_body.as_tdx() else {
+        eprintln!("Error parsing quote: not a TD quote");
+        process::exit(1);
+    };
 
     // Print the parsed data
     println!("Quote Header:");
     println!("  Version: {}", quote.header.version);
-    println!("  Attestation Key Type: {}", quote.header.attestation_key_type);
+    println!("  Attestation Key Type: {}", u16::from(quote.header.attestation_key_type));
     println!("  TEE Type: {:?}", quote.header.tee_type);
-    println!("  Reserved 1: {}", hex::encode(&quote.header.reserved1));
-    println!("  Reserved 2: {}", hex::encode(&quote.header.reserved2));
+    println!("  Reserved 1: {}", hex::encode(quote.header.reserved1));
+    println!("  Reserved 2: {}", hex::encode(quote.header.reserved2));
     println!("  QE Vendor ID: {}", quote.header.qe_vendor_id);
-    println!("  User Data: {}", hex::encode(&quote.header.user_data));
+    println!("  User Data: {}", hex::encode(quote.header.user_data));
 
     println!("TD Quote Body:");
-    println!("  TEE TCB SVN: {}", hex::encode(&quote.td_quote_body.tee_tcb_svn));
-    println!("  MRSEAM: {}", hex::encode(&quote.td_quote_body.mrseam));
-    println!("  MRSIGNERSEAM: {}", hex::encode(&quote.td_quote_body.mrsignerseam));
-    println!("  Seam Attributes: {}", hex::encode(&quote.td_quote_body.seamattributes));
-    println!("  TD Attributes: {}", hex::encode(&quote.td_quote_body.tdattributes));
-    println!("  \t{}", extract_tdattributes_info(quote.td_quote_body.tdattributes));
-    println!("  XFAM: {}", hex::encode(&quote.td_quote_body.xfam));
-    println!("  MRTD: {}", hex::encode(&quote.td_quote_body.mrtd));
-    println!("  MRCONFIGID: {}", hex::encode(&quote.td_quote_body.mrconfigid));
-    println!("  MROWNER: {}", hex::encode(&quote.td_quote_body.mrowner));
-    println!("  MROWNERCONFIG: {}", hex::encode(&quote.td_quote_body.mrownerconfig));
-    println!("  RTMR0: {}", hex::encode(&quote.td_quote_body.rtmr0));
-    println!("  RTMR1: {}", hex::encode(&quote.td_quote_body.rtmr1));
-    println!("  RTMR2: {}", hex::encode(&quote.td_quote_body.rtmr2));
-    println!("  RTMR3: {}", hex::encode(&quote.td_quote_body.rtmr3));
-    println!("  Report Data: {}", hex::encode(&quote.td_quote_body.reportdata));
+    println!("  TEE TCB SVN: {}", reversed(body.tee_tcb_svn));
+    println!("  MRSEAM: {}", reversed(body.mrseam));
+    println!("  MRSIGNERSEAM: {}", reversed(body.mrsignerseam));
+    println!("  Seam Attributes: {}", reversed(body.seamattributes));
+    let mut tdattributes = body.tdattributes;
+    tdattributes.reverse();
+    println!("  TD Attributes: {}", hex::encode(tdattributes));
+    println!("  \t{}", extract_tdattributes_info(tdattributes));
+    println!("  XFAM: {}", reversed(body.xfam));
+    println!("  MRTD: {}", reversed(body.mrtd));
+    println!("  MRCONFIGID: {}", reversed(body.mrconfigid));
+    println!("  MROWNER: {}", reversed(body.mrowner));
+    println!("  MROWNERCONFIG: {}", reversed(body.mrownerconfig));
+    println!("  RTMR0: {}", reversed(body.rtmr0));
+    println!("  RTMR1: {}", reversed(body.rtmr1));
+    println!("  RTMR2: {}", reversed(body.rtmr2));
+    println!("  RTMR3: {}", reversed(body.rtmr3));
+    println!("  Report Data: {}", reversed(body.reportdata));
 }
@@ -1,275 +1,304 @@
-use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::Cursor;
-use uuid::Uuid;
+use clap::{Parser, Subcommand};
 use std::env;
 use std::fs::File;
-use std::io::Read;
-use hex;
+use std::io::{self, IsTerminal, Read};
+use std::process;
+use tdx_quote_parser::{gunzip_if_compressed, parse_measurement_48, parse_quote_with_len, parse_quote_with_spans, HexFormat, Quote, QuoteBodyKind};
 
-#[derive(Debug)]
-enum TEEType {
-    SGX = 0x00000000,
-    TDX = 0x00000081,
+/// Parses TDX and SGX DCAP quotes (v4 and v5). A file of `-` reads the quote
+/// from stdin.
+#[derive(Parser)]
+#[command(name = "parserV5")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
 }
 
-#[derive(Debug)]
-struct QuoteHeader {
-    version: u16,
-    attestation_key_type: u16,
-    tee_type: TEEType,
-    reserved1: [u8; 2],
-    reserved2: [u8; 2],
-    qe_vendor_id: Uuid,
-    user_data: [u8; 20],
+#[derive(Subcommand)]
+enum Command {
+    /// Print every section of the quote.
+    Parse {
+        #[arg(default_value = "-")]
+        file: String,
+        /// How byte fields are rendered: plain, prefixed or grouped.
+        #[arg(long, default_value = "plain", value_parser = parse_hex_format)]
+        hex_format: HexFormat,
+        /// List every field in read order with its `[start..end]` byte range.
+        #[arg(long)]
+        raw_offsets: bool,
+        // The flags below keep the pre-subcommand interface working.
+        #[arg(long, hide = true)]
+        json: bool,
+        #[arg(long, hide = true)]
+        field: Option<String>,
+        #[arg(long, hide = true)]
+        diff: Option<String>,
+    },
+    /// Check the quote signature, QE report signature and binding, and PCK certificate chain.
+    Verify {
+        #[arg(default_value = "-")]
+        file: String,
+    },
+    /// Print the quote as JSON.
+    Json {
+        #[arg(default_value = "-")]
+        file: String,
+    },
+    /// Print the hex of a single measurement, e.g. mrtd, rtmr0 or reportdata.
+    Field {
+        name: String,
+        #[arg(default_value = "-")]
+        file: String,
+    },
+    /// List the header and report body fields that differ between two quotes.
+    Diff { file: String, other: String },
+    /// Exit 0 if the quote's MRTD equals the expected hex value, 1 otherwise.
+    VerifyMrtd {
+        #[arg(default_value = "-")]
+        file: String,
+        /// The expected MRTD as 96 hex digits, optionally `0x`-prefixed.
+        #[arg(long)]
+        expected: String,
+    },
 }
 
-#[derive(Debug)]
-struct TDQuoteBody {
-    tee_tcb_svn: [u8; 16],
-    mrseam: [u8; 48],
-    mrsignerseam: [u8; 48],
-    seamattributes: [u8; 8],
-    tdattributes: [u8; 8],
-    xfam: [u8; 8],
-    mrtd: [u8; 48],
-    mrconfigid: [u8; 48],
-    mrowner: [u8; 48],
-    mrownerconfig: [u8; 48],
-    rtmr0: [u8; 48],
-    rtmr1: [u8; 48],
-    rtmr2: [u8; 48],
-    rtmr3: [u8; 48],
-    reportdata: [u8; 64],
-    tee_tcb_svn_2: [u8; 16],
-    mrservicetd: [u8; 48],
+fn parse_hex_format(value: &str) -> Result<HexFormat, String> {
+    match value {
+        "plain" => Ok(HexFormat::Plain),
+        "prefixed" => Ok(HexFormat::Prefixed),
+        "grouped" => Ok(HexFormat::Grouped),
+        _ => Err("expected one of plain, prefixed, grouped".to_string()),
+    }
 }
 
-#[derive(Debug)]
-struct QuoteBody {
-    td_quote_body_type: u16,
-    size: u32,
-    td_quote_body: TDQuoteBody,
-}
+/// First arguments that clap handles itself; anything else is the legacy form.
+const CLAP_ARGS: [&str; 9] = ["parse", "verify", "json", "field", "diff", "verify-mrtd", "help", "-h", "--help"];
 
-#[derive(Debug)]
-struct Quote {
-    header: QuoteHeader,
-    body: QuoteBody,
-}
+fn main() {
+    // A bare `parserV5 [flags] <file>` invocation predates the subcommands and
+    // maps to `parse`, as does no argument at all when a quote is piped in.
+    let mut args: Vec<String> = env::args().collect();
+    if args.len() > 1 && !CLAP_ARGS.contains(&args[1].as_str()) {
+        args.insert(1, "parse".to_string());
+    } else if args.len() == 1 && !io::stdin().is_terminal() {
+        args.push("parse".to_string());
+    }
 
-fn parse_td_quote_body(cursor: &mut Cursor<&[u8]>) -> TDQuoteBody {
-    TDQuoteBody {
-        tee_tcb_svn: {
-            let mut buf = [0; 16];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        mrseam: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        mrsignerseam: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        seamattributes: {
-            let mut buf = [0; 8];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        tdattributes: {
-            let mut buf = [0; 8];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        xfam: {
-            let mut buf = [0; 8];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        mrtd: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        mrconfigid: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        mrowner: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        mrownerconfig: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        rtmr0: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        rtmr1: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        rtmr2: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        rtmr3: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        reportdata: {
-            let mut buf = [0; 64];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        tee_tcb_svn_2: {
-            let mut buf = [0; 16];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        mrservicetd: {
-            let mut buf = [0; 48];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
+    match Cli::parse_from(args).command {
+        Command::Parse { file, hex_format, raw_offsets, json, field, diff } => {
+            if raw_offsets {
+                print_offsets(&file);
+                return;
+            }
+            let quote = read_quote(&file);
+            if let Some(diff) = diff {
+                print_diff(&quote, &read_quote(&diff));
+            } else if let Some(field) = field {
+                print_field(&quote, &field);
+            } else if json {
+                print_json(&quote);
+            } else {
+                println!("{}", quote.display_with(hex_format));
+            }
+        }
+        Command::Verify { file } => verify(&read_quote(&file)),
+        Command::Json { file } => print_json(&read_quote(&file)),
+        Command::Field { name, file } => print_field(&read_quote(&file), &name),
+        Command::Diff { file, other } => print_diff(&read_quote(&file), &read_quote(&other)),
+        Command::VerifyMrtd { file, expected } => verify_mrtd(&read_quote(&file), &expected),
     }
 }
 
-fn parse_quote(data: &[u8]) -> Quote {
-    let mut cursor = Cursor::new(data);
-
-    let header = QuoteHeader {
-        version: cursor.read_u16::<LittleEndian>().unwrap(),
-        attestation_key_type: cursor.read_u16::<LittleEndian>().unwrap(),
-        tee_type: match cursor.read_u32::<LittleEndian>().unwrap() {
-            0x00000000 => TEEType::SGX,
-            0x00000081 => TEEType::TDX,
-            _ => panic!("Invalid TEE type"),
-        },
-        reserved1: {
-            let mut buf = [0; 2];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        reserved2: {
-            let mut buf = [0; 2];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-        qe_vendor_id: {
-            let mut buf = [0; 16];
-            cursor.read_exact(&mut buf).unwrap();
-            Uuid::from_bytes(buf)
-        },
-        user_data: {
-            let mut buf = [0; 20];
-            cursor.read_exact(&mut buf).unwrap();
-            buf
-        },
-    };
-
-    let body = QuoteBody {
-        td_quote_body_type: cursor.read_u16::<LittleEndian>().unwrap(),
-        size: cursor.read_u32::<LittleEndian>().unwrap(),
-        td_quote_body: parse_td_quote_body(&mut cursor),
+/// Reads `file_path`, or stdin if it is `-`, decompressing gzip input and
+/// exiting on failure.
+fn read_input(file_path: &str) -> Vec<u8> {
+    // Read the file contents
+    let mut file: Box<dyn Read> = if file_path == "-" {
+        Box::new(io::stdin().lock())
+    } else {
+        match File::open(file_path) {
+            Ok(file) => Box::new(file),
+            Err(err) => {
+                eprintln!("Error opening file: {}", err);
+                process::exit(1);
+            }
+        }
     };
+    let mut file_contents = Vec::new();
+    if let Err(err) = file.read_to_end(&mut file_contents) {
+        eprintln!("Error reading file: {}", err);
+        process::exit(1);
+    }
 
-    Quote { header, body }
+    match gunzip_if_compressed(&file_contents) {
+        Ok(contents) => contents.into_owned(),
+        Err(err) => {
+            eprintln!("Error decompressing {}: {}", file_path, err);
+            process::exit(1);
+        }
+    }
 }
 
-fn extract_tdattributes_info(tdattributes: [u8; 8]) -> String {
-    let tud = tdattributes[0];
-    let sec = u32::from_le_bytes([0, tdattributes[1], tdattributes[2], tdattributes[3]]);
-    let other = u64::from_le_bytes([0, 0, 0, 0, tdattributes[4], tdattributes[5], tdattributes[6], tdattributes[7]]);
-
-    // Extract and format TUD flags
-    let debug = if tud & 0b00000001 != 0 { "True" } else { "False" };
-    let tud_reserved = (tud >> 1) & 0b01111111; // Extract reserved bits
-    let tud_flags = format!("TUD:\n\t   DEBUG: {}\n\t   RESERVED: {}", debug, tud_reserved);
+/// Reads and parses the quote in `file_path`, or stdin if it is `-`, exiting
+/// on failure.
+fn read_quote(file_path: &str) -> Quote {
+    let file_contents = read_input(file_path);
 
-    // Extract and format SEC flags
-    let sec_reserved = (sec >> 8) & 0b00001111_11111111_11111111; // Extract reserved bits
-    let sept_ve_disable = (sec >> 27) & 0b00000001;
-    let pks = (sec >> 30) & 0b00000001;
-    let kl = (sec >> 31) & 0b00000001;
-    let sec_flags = format!("\tSEC:\n\t  RESERVED: {}\n\t  SEPT_VE_DISABLE: {}\n\t  PKS: {}\n\t  KL: {}", sec_reserved, sept_ve_disable, pks, kl);
+    // Parse the quote
+    match parse_quote_with_len(&file_contents) {
+        Ok((quote, len)) => {
+            if len < file_contents.len() {
+                eprintln!("Warning: {} trailing bytes after the quote", file_contents.len() - len);
+            }
+            quote
+        }
+        Err(err) => {
+            eprintln!("Error parsing quote {}: {}", file_path, err);
+            process::exit(1);
+        }
+    }
+}
 
-    // Extract and format OTHER flags
-    let other_reserved = (other >> 32) & 0b01111111_11111111_11111111_11111111; // Extract reserved bits
-    let perfmon = (other >> 63) & 0b00000001;
-    let other_flags = format!("\tOTHER:\n\t  RESERVED: {}\n\t  PERFMON: {}", other_reserved, perfmon);
+/// Values longer than this are shown by length only in `--raw-offsets` output.
+const MAX_OFFSET_VALUE_LEN: usize = 64;
 
-    format!("{}\n{}\n{}", tud_flags, sec_flags, other_flags)
+/// Prints the byte range of every field of the quote in `file_path`, with its
+/// value unless it is a long section such as the certification data.
+fn print_offsets(file_path: &str) {
+    let file_contents = read_input(file_path);
+    let spans = match parse_quote_with_spans(&file_contents) {
+        Ok((quote, spans)) => {
+            if quote.encoded_len() < file_contents.len() {
+                eprintln!("Warning: {} trailing bytes after the quote", file_contents.len() - quote.encoded_len());
+            }
+            spans
+        }
+        Err(err) => {
+            eprintln!("Error parsing quote {}: {}", file_path, err);
+            process::exit(1);
+        }
+    };
+    for span in &spans {
+        let range = format!("[{}..{}]", span.offset, span.offset + span.len);
+        let bytes = &file_contents[span.offset..span.offset + span.len];
+        if span.len <= MAX_OFFSET_VALUE_LEN {
+            println!("{:<14} {:<24} {}", range, span.name, hex::encode(bytes));
+        } else {
+            println!("{:<14} {:<24} ({} bytes)", range, span.name, span.len);
+        }
+    }
 }
 
-fn main() {
-    // Get the file path from the command line arguments
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        println!("Usage: ./binary <file_path>");
+/// Prints the fields that differ between two quotes, exiting with status 1 if
+/// there are any.
+fn print_diff(quote: &Quote, other: &Quote) {
+    let diffs = quote.diff(other);
+    if diffs.is_empty() {
+        println!("Quotes have identical headers and report bodies");
         return;
     }
-    let file_path = &args[1];
+    for diff in &diffs {
+        println!("{}:\n  - {}\n  + {}", diff.field, diff.left, diff.right);
+    }
+    process::exit(1);
+}
 
-    // Read the file contents
-    let mut file = match File::open(file_path) {
-        Ok(file) => file,
+/// Compares the MRTD with `expected`, exiting with status 1 and printing
+/// both values if they differ, or 2 if the quote has no MRTD.
+fn verify_mrtd(quote: &Quote, expected: &str) {
+    let expected = match parse_measurement_48(expected) {
+        Ok(expected) => expected,
         Err(err) => {
-            println!("Error opening file: {}", err);
-            return;
+            eprintln!("Invalid expected MRTD: {}", err);
+            process::exit(2);
         }
     };
-    let mut file_contents = Vec::new();
-    if let Err(err) = file.read_to_end(&mut file_contents) {
-        println!("Error reading file: {}", err);
+    let body = match quote.body.td_quote_body.as_tdx() {
+        Some(body) => body,
+        None => {
+            eprintln!("Quote does not carry a TD report body");
+            process::exit(2);
+        }
+    };
+    if body.mrtd == expected {
+        println!("MRTD matches: {}", hex::encode(body.mrtd));
         return;
     }
+    println!("MRTD mismatch:\n  expected: {}\n  actual:   {}", hex::encode(expected), hex::encode(body.mrtd));
+    process::exit(1);
+}
 
-    // Parse the quote
-    let quote = parse_quote(&file_contents);
+/// Prints the hex of a single measurement field, exiting non-zero if the name
+/// is unknown or the quote does not carry the field.
+fn print_field(quote: &Quote, name: &str) {
+    let value: Option<&[u8]> = match &quote.body.td_quote_body {
+        QuoteBodyKind::Tdx(body) => match name {
+            "tee_tcb_svn" => Some(&body.tee_tcb_svn),
+            "mrseam" => Some(&body.mrseam),
+            "mrsignerseam" => Some(&body.mrsignerseam),
+            "seamattributes" => Some(&body.seamattributes),
+            "tdattributes" => Some(&body.tdattributes),
+            "xfam" => Some(&body.xfam),
+            "mrtd" => Some(&body.mrtd),
+            "mrconfigid" => Some(&body.mrconfigid),
+            "mrowner" => Some(&body.mrowner),
+            "mrownerconfig" => Some(&body.mrownerconfig),
+            "rtmr0" => Some(&body.rtmr0),
+            "rtmr1" => Some(&body.rtmr1),
+            "rtmr2" => Some(&body.rtmr2),
+            "rtmr3" => Some(&body.rtmr3),
+            "reportdata" => Some(&body.reportdata),
+            "tee_tcb_svn_2" => body.tee_tcb_svn_2.as_ref().map(|v| &v[..]),
+            "mrservicetd" => body.mrservicetd.as_ref().map(|v| &v[..]),
+            _ => None,
+        },
+        QuoteBodyKind::Sgx(body) => match name {
+            "cpusvn" => Some(&body.cpu_svn),
+            "attributes" => Some(&body.attributes),
+            "mrenclave" => Some(&body.mr_enclave),
+            "mrsigner" => Some(&body.mr_signer),
+            "reportdata" => Some(&body.report_data),
+            _ => None,
+        },
+    };
+    match value {
+        Some(value) => println!("{}", hex::encode(value)),
+        None => {
+            eprintln!("Unknown field for this quote: {}", name);
+            process::exit(2);
+        }
+    }
+}
 
-    // Print the parsed data
-    println!("Quote Header:");
-    println!("  Version: {}", quote.header.version);
-    println!("  Attestation Key Type: {}", quote.header.attestation_key_type);
-    println!("  TEE Type: {:?}", quote.header.tee_type);
-    println!("  Reserved 1: {}", hex::encode(&quote.header.reserved1));
-    println!("  Reserved 2: {}", hex::encode(&quote.header.reserved2));
-    println!("  QE Vendor ID: {}", quote.header.qe_vendor_id);
-    println!("  User Data: {}", hex::encode(&quote.header.user_data));
-    
+fn print_json(quote: &Quote) {
+    match serde_json::to_string_pretty(quote) {
+        Ok(json) => println!("{}", json),
+        Err(err) => {
+            eprintln!("Error encoding JSON: {}", err);
+            process::exit(1);
+        }
+    }
+}
 
-    println!("Quote Body:");
-    println!("  TD Quote Body Type: {}", quote.body.td_quote_body_type);
-    println!("  Size: {}", quote.body.size);
-    println!("  TEE TCB SVN: {}", hex::encode(&quote.body.td_quote_body.tee_tcb_svn));
-    println!("  MRSEAM: {}", hex::encode(&quote.body.td_quote_body.mrseam));
-    println!("  MRSIGNERSEAM: {}", hex::encode(&quote.body.td_quote_body.mrsignerseam));
-    println!("  Seam Attributes: {}", hex::encode(&quote.body.td_quote_body.seamattributes));
-    println!("  TD Attributes: {}", hex::encode(&quote.body.td_quote_body.tdattributes));
-    println!("  \t{}", extract_tdattributes_info(quote.body.td_quote_body.tdattributes));
-    println!("  XFAM: {}", hex::encode(&quote.body.td_quote_body.xfam));
-    println!("  MRTD: {}", hex::encode(&quote.body.td_quote_body.mrtd));
-    println!("  MRCONFIGID: {}", hex::encode(&quote.body.td_quote_body.mrconfigid));
-    println!("  MROWNER: {}", hex::encode(&quote.body.td_quote_body.mrowner));
-    println!("  MROWNERCONFIG: {}", hex::encode(&quote.body.td_quote_body.mrownerconfig));
-    println!("  RTMR0: {}", hex::encode(&quote.body.td_quote_body.rtmr0));
-    println!("  RTMR1: {}", hex::encode(&quote.body.td_quote_body.rtmr1));
-    println!("  RTMR2: {}", hex::encode(&quote.body.td_quote_body.rtmr2));
-    println!("  RTMR3: {}", hex::encode(&quote.body.td_quote_body.rtmr3));
-    println!("  Report Data: {}", hex::encode(&quote.body.td_quote_body.reportdata));
-    println!("  TEE TCB SVN 2: {}", hex::encode(&quote.body.td_quote_body.tee_tcb_svn_2));
-    println!("  MRSERVICETD: {}", hex::encode(&quote.body.td_quote_body.mrservicetd));
+/// Runs each verification step, exiting with status 1 if any fails.
+fn verify(quote: &Quote) {
+    let checks = [
+        ("Quote signature", quote.verify_signature()),
+        ("QE report signature", quote.verify_qe_report_signature()),
+        ("QE report binding", quote.verify_qe_report_binding()),
+        ("PCK certificate chain", quote.verify_cert_chain(tdx_quote_parser::INTEL_SGX_ROOT_CA)),
+    ];
+    let mut failed = false;
+    for (name, result) in checks {
+        match result {
+            Ok(()) => println!("{}: OK", name),
+            Err(err) => {
+                println!("{}: FAILED ({})", name, err);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,176 @@
+//! Zero-copy view of a quote.
+
+use crate::layout::*;
+use crate::{
+    attestation_key_len, check_body_size, parse_sgx_quote_body, read_header, v4_body_type, ParseError, QuoteBodyType, QuoteHeader,
+    Reader, SgxQuoteBody,
+};
+
+/// TD report body whose byte fields point into the original buffer.
+#[derive(Clone)]
+pub struct TDQuoteBodyRef<'a> {
+    pub tee_tcb_svn: &'a [u8; TEE_TCB_SVN_LEN],
+    pub mrseam: &'a [u8; MRSEAM_LEN],
+    pub mrsignerseam: &'a [u8; MRSIGNERSEAM_LEN],
+    pub seamattributes: &'a [u8; SEAM_ATTRIBUTES_LEN],
+    pub tdattributes: &'a [u8; TD_ATTRIBUTES_LEN],
+    pub xfam: &'a [u8; XFAM_LEN],
+    pub mrtd: &'a [u8; MRTD_LEN],
+    pub mrconfigid: &'a [u8; MRCONFIGID_LEN],
+    pub mrowner: &'a [u8; MROWNER_LEN],
+    pub mrownerconfig: &'a [u8; MROWNERCONFIG_LEN],
+    pub rtmr0: &'a [u8; RTMR_LEN],
+    pub rtmr1: &'a [u8; RTMR_LEN],
+    pub rtmr2: &'a [u8; RTMR_LEN],
+    pub rtmr3: &'a [u8; RTMR_LEN],
+    pub reportdata: &'a [u8; REPORT_DATA_LEN],
+    pub tee_tcb_svn_2: Option<&'a [u8; TEE_TCB_SVN_LEN]>,
+    pub mrservicetd: Option<&'a [u8; MRSERVICETD_LEN]>,
+}
+
+/// Borrowed counterpart of [`crate::QuoteBodyKind`].
+///
+/// SGX report bodies are small and decoded by value.
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum QuoteBodyKindRef<'a> {
+    Sgx(SgxQuoteBody),
+    Tdx(TDQuoteBodyRef<'a>),
+}
+
+/// A quote whose report body and signature borrow from the input buffer.
+#[derive(Clone)]
+pub struct QuoteRef<'a> {
+    pub header: QuoteHeader,
+    pub td_quote_body_type: QuoteBodyType,
+    pub size: u32,
+    pub td_quote_body: QuoteBodyKindRef<'a>,
+    pub signature_data_len: u32,
+    pub signature: &'a [u8],
+    pub attestation_key: &'a [u8],
+    signed_data: &'a [u8],
+}
+
+hex_debug!(TDQuoteBodyRef<'a> {
+    tee_tcb_svn: hex,
+    mrseam: hex,
+    mrsignerseam: hex,
+    seamattributes: hex,
+    tdattributes: hex,
+    xfam: hex,
+    mrtd: hex,
+    mrconfigid: hex,
+    mrowner: hex,
+    mrownerconfig: hex,
+    rtmr0: hex,
+    rtmr1: hex,
+    rtmr2: hex,
+    rtmr3: hex,
+    reportdata: hex,
+    tee_tcb_svn_2: opt_hex,
+    mrservicetd: opt_hex,
+});
+
+hex_debug!(QuoteRef<'a> {
+    header,
+    td_quote_body_type,
+    size,
+    td_quote_body,
+    signature_data_len,
+    signature: hex,
+    attestation_key: hex,
+    signed_data: hex,
+});
+
+fn parse_td_quote_body_ref<'a>(reader: &mut Reader<'a>, tdx15: bool) -> Result<TDQuoteBodyRef<'a>, ParseError> {
+    let mut body = TDQuoteBodyRef {
+        tee_tcb_svn: reader.take("tee_tcb_svn")?,
+        mrseam: reader.take("mrseam")?,
+        mrsignerseam: reader.take("mrsignerseam")?,
+        seamattributes: reader.take("seamattributes")?,
+        tdattributes: reader.take("tdattributes")?,
+        xfam: reader.take("xfam")?,
+        mrtd: reader.take("mrtd")?,
+        mrconfigid: reader.take("mrconfigid")?,
+        mrowner: reader.take("mrowner")?,
+        mrownerconfig: reader.take("mrownerconfig")?,
+        rtmr0: reader.take("rtmr0")?,
+        rtmr1: reader.take("rtmr1")?,
+        rtmr2: reader.take("rtmr2")?,
+        rtmr3: reader.take("rtmr3")?,
+        reportdata: reader.take("reportdata")?,
+        tee_tcb_svn_2: None,
+        mrservicetd: None,
+    };
+    if tdx15 {
+        body.tee_tcb_svn_2 = Some(reader.take("tee_tcb_svn_2")?);
+        body.mrservicetd = Some(reader.take("mrservicetd")?);
+    }
+    Ok(body)
+}
+
+fn parse_body_ref<'a>(reader: &mut Reader<'a>, body_type: QuoteBodyType) -> Result<QuoteBodyKindRef<'a>, ParseError> {
+    Ok(match body_type {
+        QuoteBodyType::SgxReport => QuoteBodyKindRef::Sgx(parse_sgx_quote_body(reader)?),
+        QuoteBodyType::Tdx10 => QuoteBodyKindRef::Tdx(parse_td_quote_body_ref(reader, false)?),
+        QuoteBodyType::Tdx15 => QuoteBodyKindRef::Tdx(parse_td_quote_body_ref(reader, true)?),
+        QuoteBodyType::Unknown(body_type) => return Err(ParseError::UnsupportedBodyType(body_type)),
+    })
+}
+
+#[cfg(feature = "alloc")]
+impl crate::Quote {
+    /// Parses a quote without copying the report body or signature.
+    ///
+    /// See [`QuoteRef::parse`].
+    pub fn parse_borrowed(data: &[u8]) -> Result<QuoteRef<'_>, ParseError> {
+        QuoteRef::parse(data)
+    }
+}
+
+impl<'a> QuoteRef<'a> {
+    /// Parses a quote without copying the report body or signature. This does
+    /// not allocate and is available without the `alloc` feature.
+    ///
+    /// Once the body type is known, the buffer length is checked against the
+    /// full body and signature layout before any of it is sliced.
+    pub fn parse(data: &'a [u8]) -> Result<Self, ParseError> {
+        let mut reader = Reader::new(data);
+
+        let header = read_header(&mut reader)?;
+        let key_len = attestation_key_len(header.attestation_key_type)?;
+        let version = header.version;
+        let (td_quote_body_type, size) = if version == 4 {
+            let td_quote_body_type = v4_body_type(header.tee_type)?;
+            (td_quote_body_type, td_quote_body_type.body_size().unwrap_or_default() as u32)
+        } else {
+            let td_quote_body_type = QuoteBodyType::from(reader.read_u16("td_quote_body_type")?);
+            let size = reader.read_u32("size")?;
+            check_body_size(td_quote_body_type, size)?;
+            (td_quote_body_type, size)
+        };
+        debug!("quote body type {}, {} bytes at offset {}", td_quote_body_type, size, reader.offset());
+        let td_quote_body = parse_body_ref(&mut reader, td_quote_body_type)?;
+        let signed_data = &data[..reader.offset()];
+
+        // Only the signature and attestation key are read, but neither may
+        // run past the declared signature data length.
+        let signature_data_len = reader.read_u32("signature_data_len")?;
+        let mut section = reader.section_prefix(signature_data_len as usize);
+        Ok(QuoteRef {
+            header,
+            td_quote_body_type,
+            size,
+            td_quote_body,
+            signature_data_len,
+            signature: section.read_slice(key_len, "signature")?,
+            attestation_key: section.read_slice(key_len, "attestation_key")?,
+            signed_data,
+        })
+    }
+
+    /// The header and body bytes covered by the quote signature.
+    pub fn signed_data(&self) -> &'a [u8] {
+        self.signed_data
+    }
+}
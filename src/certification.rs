@@ -0,0 +1,155 @@
+//! Certification data carried at the end of the quote signature section.
+
+use alloc::{string::String, vec::Vec};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::display::{Hex, HexList};
+use crate::{HexFormat, ParseError, Reader};
+
+/// Certification data type carrying the concatenated PEM PCK certificate chain.
+pub const PCK_CERT_CHAIN_CERT_DATA_TYPE: u16 = 5;
+
+const PPID_CLEARTEXT_CERT_DATA_TYPE: u16 = 1;
+const PPID_RSA2048_CERT_DATA_TYPE: u16 = 2;
+const PPID_RSA3072_CERT_DATA_TYPE: u16 = 3;
+const PCK_LEAF_CERT_CERT_DATA_TYPE: u16 = 4;
+
+const PEM_BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+const PEM_END: &str = "-----END CERTIFICATE-----";
+
+/// Type-tagged certification data used to verify the QE report signature.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CertificationData {
+    pub cert_data_type: u16,
+    pub size: u32,
+    /// The raw certification data as it appears in the quote.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec"))]
+    pub data: Vec<u8>,
+}
+
+/// PCK identifier carried by certification data types 1 to 3.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PckId {
+    /// The PPID, in plain text for type 1 and RSA-OAEP encrypted otherwise.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec"))]
+    pub ppid: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub cpusvn: [u8; 16],
+    pub pcesvn: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub pceid: [u8; 2],
+}
+
+/// Certification data decoded according to its type.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CertificationDataKind {
+    /// Type 1: plain text PPID.
+    PpidCleartext(PckId),
+    /// Type 2: PPID encrypted with RSA-2048-OAEP.
+    PpidRsa2048(PckId),
+    /// Type 3: PPID encrypted with RSA-3072-OAEP.
+    PpidRsa3072(PckId),
+    /// Type 4: the PCK leaf certificate, as raw bytes.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec"))]
+    PckLeafCert(Vec<u8>),
+    /// Type 5: the DER encoded PCK certificate chain, leaf first.
+    PckCertChain(Vec<Vec<u8>>),
+}
+
+impl fmt::Debug for CertificationDataKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CertificationDataKind::PpidCleartext(id) => f.debug_tuple("PpidCleartext").field(id).finish(),
+            CertificationDataKind::PpidRsa2048(id) => f.debug_tuple("PpidRsa2048").field(id).finish(),
+            CertificationDataKind::PpidRsa3072(id) => f.debug_tuple("PpidRsa3072").field(id).finish(),
+            CertificationDataKind::PckLeafCert(der) => {
+                f.debug_tuple("PckLeafCert").field(&Hex(der, HexFormat::Plain)).finish()
+            }
+            CertificationDataKind::PckCertChain(chain) => f.debug_tuple("PckCertChain").field(&HexList(chain)).finish(),
+        }
+    }
+}
+
+fn parse_pck_id(data: &[u8], ppid_len: usize) -> Result<PckId, ParseError> {
+    let mut reader = Reader::new(data);
+    Ok(PckId {
+        ppid: reader.read_slice(ppid_len, "ppid")?.to_vec(),
+        cpusvn: reader.read_bytes("cpusvn")?,
+        pcesvn: reader.read_u16("pcesvn")?,
+        pceid: reader.read_bytes("pceid")?,
+    })
+}
+
+impl CertificationData {
+    /// Decodes the certification data according to `cert_data_type`.
+    pub fn decode(&self) -> Result<CertificationDataKind, ParseError> {
+        Ok(match self.cert_data_type {
+            PPID_CLEARTEXT_CERT_DATA_TYPE => CertificationDataKind::PpidCleartext(parse_pck_id(&self.data, 16)?),
+            PPID_RSA2048_CERT_DATA_TYPE => CertificationDataKind::PpidRsa2048(parse_pck_id(&self.data, 256)?),
+            PPID_RSA3072_CERT_DATA_TYPE => CertificationDataKind::PpidRsa3072(parse_pck_id(&self.data, 384)?),
+            PCK_LEAF_CERT_CERT_DATA_TYPE => CertificationDataKind::PckLeafCert(self.data.clone()),
+            PCK_CERT_CHAIN_CERT_DATA_TYPE => CertificationDataKind::PckCertChain(split_pem_chain(&self.data)?),
+            other => return Err(ParseError::UnsupportedCertificationDataType(other)),
+        })
+    }
+
+    /// Returns the DER encoded PCK certificate chain, leaf first.
+    ///
+    /// Returns `None` unless `cert_data_type` is 5.
+    pub fn pck_cert_chain(&self) -> Option<Result<Vec<Vec<u8>>, ParseError>> {
+        if self.cert_data_type != PCK_CERT_CHAIN_CERT_DATA_TYPE {
+            return None;
+        }
+        Some(split_pem_chain(&self.data))
+    }
+}
+
+/// Decodes the first PEM certificate in `text`, returning its DER encoding
+/// and the text after it, or `None` if there is none.
+fn next_pem_cert(text: &str) -> Result<Option<(Vec<u8>, &str)>, ParseError> {
+    let Some(begin) = text.find(PEM_BEGIN) else {
+        return Ok(None);
+    };
+    let body = &text[begin + PEM_BEGIN.len()..];
+    let end = body.find(PEM_END).ok_or(ParseError::InvalidCertChain)?;
+    let base64: String = body[..end].chars().filter(|c| !c.is_ascii_whitespace()).collect();
+    let der = STANDARD.decode(base64).map_err(|_| ParseError::InvalidCertChain)?;
+    Ok(Some((der, &body[end + PEM_END.len()..])))
+}
+
+/// Splits a PEM blob into the DER encoding of each certificate it contains.
+fn split_pem_chain(data: &[u8]) -> Result<Vec<Vec<u8>>, ParseError> {
+    let mut rest = core::str::from_utf8(data).map_err(|_| ParseError::InvalidCertChain)?;
+    let mut certs = Vec::new();
+    while let Some((der, after)) = next_pem_cert(rest)? {
+        certs.push(der);
+        rest = after;
+    }
+    if certs.is_empty() {
+        return Err(ParseError::InvalidCertChain);
+    }
+    Ok(certs)
+}
+
+/// The DER encoding of the first certificate of a type 5 PEM chain, the PCK
+/// leaf, without decoding the certificates after it.
+#[cfg(feature = "verify")]
+pub(crate) fn first_pem_cert(data: &[u8]) -> Result<Vec<u8>, ParseError> {
+    let text = core::str::from_utf8(data).map_err(|_| ParseError::InvalidCertChain)?;
+    next_pem_cert(text)?.map(|(der, _)| der).ok_or(ParseError::InvalidCertChain)
+}
+
+pub(crate) fn parse_certification_data(reader: &mut Reader<'_>) -> Result<CertificationData, ParseError> {
+    let cert_data_type = reader.read_u16("cert_data_type")?;
+    let size = reader.read_u32("size")?;
+    let data = reader.read_slice(size as usize, "certification_data")?.to_vec();
+    Ok(CertificationData { cert_data_type, size, data })
+}
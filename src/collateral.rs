@@ -0,0 +1,536 @@
+//! Intel PCS collateral and TCB status evaluation.
+//!
+//! [`TcbInfo`] and [`QeIdentity`] deserialize the JSON returned by the PCS
+//! `tcb` and `qe/identity` endpoints without checking their signatures.
+//! [`verify_quote`] checks them against the TCB signing chain of a
+//! [`Collateral`] before using them.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::time::Duration;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use x509_cert::der::DateTime;
+
+use crate::layout::{MRSIGNERSEAM_LEN, SEAM_ATTRIBUTES_LEN};
+use crate::verify::{tcb_signing_key, verify_collateral_signature, ChainVerifier};
+use crate::{parse_quote, ParseError, Quote, QuoteBodyKind, TDQuoteBody, VerifyError, INTEL_SGX_ROOT_CA};
+
+/// TCB status of a platform, TDX module or QE as reported by Intel, ordered
+/// from the best status to the worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum TcbStatus {
+    UpToDate,
+    #[serde(rename = "SWHardeningNeeded")]
+    SwHardeningNeeded,
+    ConfigurationNeeded,
+    #[serde(rename = "ConfigurationAndSWHardeningNeeded")]
+    ConfigurationAndSwHardeningNeeded,
+    OutOfDate,
+    OutOfDateConfigurationNeeded,
+    Revoked,
+}
+
+impl TcbStatus {
+    /// Whether the status is up to date, possibly with configuration or
+    /// software hardening advice, as opposed to out of date or revoked.
+    pub fn is_acceptable(self) -> bool {
+        !matches!(self, TcbStatus::OutOfDate | TcbStatus::OutOfDateConfigurationNeeded | TcbStatus::Revoked)
+    }
+}
+
+/// One SVN of `sgxtcbcomponents` or `tdxtcbcomponents`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TcbComponent {
+    pub svn: u8,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default, rename = "type")]
+    pub kind: Option<String>,
+}
+
+/// Minimum component SVNs of a TCB level.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Tcb {
+    pub sgxtcbcomponents: Vec<TcbComponent>,
+    pub pcesvn: u16,
+    /// Empty in SGX TCB info.
+    #[serde(default)]
+    pub tdxtcbcomponents: Vec<TcbComponent>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TcbLevel {
+    pub tcb: Tcb,
+    pub tcb_date: String,
+    pub tcb_status: TcbStatus,
+    #[serde(default, rename = "advisoryIDs")]
+    pub advisory_ids: Vec<String>,
+}
+
+/// The `tdxModule` object of TDX TCB info, describing the TDX module of
+/// modules with major version 0.
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TdxModule {
+    #[serde(with = "crate::serde_hex")]
+    pub mrsigner: [u8; MRSIGNERSEAM_LEN],
+    #[serde(with = "crate::serde_hex")]
+    pub attributes: [u8; SEAM_ATTRIBUTES_LEN],
+    #[serde(with = "crate::serde_hex")]
+    pub attributes_mask: [u8; SEAM_ATTRIBUTES_LEN],
+}
+
+hex_debug!(TdxModule { mrsigner: hex, attributes: hex, attributes_mask: hex });
+
+/// An entry of `tdxModuleIdentities`, describing one TDX module major version.
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TdxModuleIdentity {
+    /// `"TDX_"` followed by the two-digit major version, e.g. `"TDX_01"`.
+    pub id: String,
+    #[serde(with = "crate::serde_hex")]
+    pub mrsigner: [u8; MRSIGNERSEAM_LEN],
+    #[serde(with = "crate::serde_hex")]
+    pub attributes: [u8; SEAM_ATTRIBUTES_LEN],
+    #[serde(with = "crate::serde_hex")]
+    pub attributes_mask: [u8; SEAM_ATTRIBUTES_LEN],
+    /// Ordered from the highest module SVN to the lowest, with the SVN in `isvsvn`.
+    pub tcb_levels: Vec<QeTcbLevel>,
+}
+
+hex_debug!(TdxModuleIdentity { id, mrsigner: hex, attributes: hex, attributes_mask: hex, tcb_levels });
+
+/// The `tcbInfo` object of a PCS TCB info response.
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TcbInfo {
+    /// `"SGX"` or `"TDX"`; absent before version 3.
+    #[serde(default)]
+    pub id: Option<String>,
+    pub version: u32,
+    pub issue_date: String,
+    pub next_update: String,
+    #[serde(with = "crate::serde_hex")]
+    pub fmspc: [u8; 6],
+    #[serde(with = "crate::serde_hex")]
+    pub pce_id: [u8; 2],
+    pub tcb_evaluation_data_number: u32,
+    /// Only in TDX TCB info.
+    #[serde(default)]
+    pub tdx_module: Option<TdxModule>,
+    /// Only in TDX TCB info.
+    #[serde(default)]
+    pub tdx_module_identities: Vec<TdxModuleIdentity>,
+    /// Ordered from the highest TCB to the lowest.
+    pub tcb_levels: Vec<TcbLevel>,
+}
+
+hex_debug!(TcbInfo {
+    id,
+    version,
+    issue_date,
+    next_update,
+    fmspc: hex,
+    pce_id: hex,
+    tcb_evaluation_data_number,
+    tdx_module,
+    tdx_module_identities,
+    tcb_levels,
+});
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TcbInfoResponse {
+    tcb_info: TcbInfo,
+}
+
+impl TcbInfo {
+    /// Parses a PCS TCB info response, `{"tcbInfo": {...}, "signature": "..."}`.
+    pub fn from_json(json: &str) -> Result<TcbInfo, ParseError> {
+        let response: TcbInfoResponse = serde_json::from_str(json).map_err(ParseError::InvalidCollateral)?;
+        Ok(response.tcb_info)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QeTcb {
+    pub isvsvn: u16,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QeTcbLevel {
+    pub tcb: QeTcb,
+    pub tcb_date: String,
+    pub tcb_status: TcbStatus,
+}
+
+/// The `enclaveIdentity` object of a PCS QE identity response.
+///
+/// `miscselect` and `attributes` are compared byte for byte with the
+/// little-endian encoding of the QE report fields.
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QeIdentity {
+    /// `"QE"` or `"TD_QE"`.
+    pub id: String,
+    pub version: u32,
+    pub issue_date: String,
+    pub next_update: String,
+    pub tcb_evaluation_data_number: u32,
+    #[serde(with = "crate::serde_hex", rename = "miscselect")]
+    pub miscselect: [u8; 4],
+    #[serde(with = "crate::serde_hex", rename = "miscselectMask")]
+    pub miscselect_mask: [u8; 4],
+    #[serde(with = "crate::serde_hex")]
+    pub attributes: [u8; 16],
+    #[serde(with = "crate::serde_hex")]
+    pub attributes_mask: [u8; 16],
+    #[serde(with = "crate::serde_hex", rename = "mrsigner")]
+    pub mrsigner: [u8; 32],
+    #[serde(rename = "isvprodid")]
+    pub isvprodid: u16,
+    /// Ordered from the highest ISV SVN to the lowest.
+    pub tcb_levels: Vec<QeTcbLevel>,
+}
+
+hex_debug!(QeIdentity {
+    id,
+    version,
+    issue_date,
+    next_update,
+    tcb_evaluation_data_number,
+    miscselect: hex,
+    miscselect_mask: hex,
+    attributes: hex,
+    attributes_mask: hex,
+    mrsigner: hex,
+    isvprodid,
+    tcb_levels,
+});
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QeIdentityResponse {
+    enclave_identity: QeIdentity,
+}
+
+impl QeIdentity {
+    /// Parses a PCS QE identity response, `{"enclaveIdentity": {...}, "signature": "..."}`.
+    pub fn from_json(json: &str) -> Result<QeIdentity, ParseError> {
+        let response: QeIdentityResponse = serde_json::from_str(json).map_err(ParseError::InvalidCollateral)?;
+        Ok(response.enclave_identity)
+    }
+}
+
+/// Whether every SVN in `svns` is at least the level's component SVN.
+fn components_at_least(svns: &[u8], components: &[TcbComponent]) -> bool {
+    svns.len() >= components.len() && svns.iter().zip(components).all(|(svn, component)| *svn >= component.svn)
+}
+
+fn masked_eq(value: &[u8], expected: &[u8], mask: &[u8]) -> bool {
+    value.iter().zip(expected).zip(mask).all(|((value, expected), mask)| value & mask == expected & mask)
+}
+
+/// Checks the TDX module of `body` against `tcb_info`, as described for
+/// [`Quote::tcb_status`], returning `UpToDate` when there is no module status.
+fn tdx_module_status(body: &TDQuoteBody, tcb_info: &TcbInfo) -> Result<TcbStatus, VerifyError> {
+    let [module_svn, major_version, ..] = body.tee_tcb_svn;
+    if major_version == 0 {
+        return match &tcb_info.tdx_module {
+            Some(module) if body.mrsignerseam != module.mrsigner
+                || !masked_eq(&body.seamattributes, &module.attributes, &module.attributes_mask) =>
+            {
+                Err(VerifyError::TdxModuleMismatch)
+            }
+            _ => Ok(TcbStatus::UpToDate),
+        };
+    }
+    let id = format!("TDX_{:02}", major_version);
+    let identity = tcb_info
+        .tdx_module_identities
+        .iter()
+        .find(|identity| identity.id == id)
+        .ok_or(VerifyError::TdxModuleMismatch)?;
+    if body.mrsignerseam != identity.mrsigner || !masked_eq(&body.seamattributes, &identity.attributes, &identity.attributes_mask) {
+        return Err(VerifyError::TdxModuleMismatch);
+    }
+    identity
+        .tcb_levels
+        .iter()
+        .find(|level| u16::from(module_svn) >= level.tcb.isvsvn)
+        .map(|level| level.tcb_status)
+        .ok_or(VerifyError::TcbLevelNotFound)
+}
+
+impl Quote {
+    /// Evaluates the platform TCB status against `tcb_info`.
+    ///
+    /// The TCB info must be for the quote's FMSPC and PCE ID, and have the id
+    /// `"TDX"` for a TD quote or `"SGX"`, or none, for an enclave quote.
+    ///
+    /// The SGX components and PCE SVN come from the PCK leaf certificate and
+    /// the TDX components from `tee_tcb_svn`. The first TCB level that all of
+    /// them meet gives the status; a TD quote only matches levels listing TDX
+    /// components.
+    ///
+    /// When the TDX module major version (`tee_tcb_svn[1]`) is non-zero, the
+    /// first two TDX components describe the module and are skipped in those
+    /// levels. The module is instead looked up in `tdx_module_identities` by
+    /// that version, must match its `mrsigner` and masked `attributes`, and
+    /// its SVN (`tee_tcb_svn[0]`) gives a module status from the identity's
+    /// levels. The worse of the platform and module status is returned.
+    /// For major version 0, only the `tdx_module` signer and attributes are
+    /// checked, if given.
+    pub fn tcb_status(&self, tcb_info: &TcbInfo) -> Result<TcbStatus, VerifyError> {
+        let pck = match self.certification_data().pck_extensions() {
+            Some(Ok(pck)) => pck,
+            Some(Err(_)) => return Err(VerifyError::InvalidCertificate),
+            None => return Err(VerifyError::MissingPckCertChain),
+        };
+        if pck.fmspc != tcb_info.fmspc {
+            return Err(VerifyError::FmspcMismatch);
+        }
+        let tee_tcb_svn = match &self.body.td_quote_body {
+            QuoteBodyKind::Tdx(body) => Some(body.tee_tcb_svn),
+            QuoteBodyKind::Sgx(_) => None,
+        };
+        let id_ok = match tee_tcb_svn {
+            Some(_) => tcb_info.id.as_deref() == Some("TDX"),
+            None => matches!(tcb_info.id.as_deref(), None | Some("SGX")),
+        };
+        if !id_ok || pck.pceid != tcb_info.pce_id {
+            return Err(VerifyError::TcbInfoMismatch);
+        }
+
+        let platform_status = tcb_info
+            .tcb_levels
+            .iter()
+            .find(|level| {
+                let tdx = &level.tcb.tdxtcbcomponents;
+                let tdx_ok = match tee_tcb_svn {
+                    Some(svn) => {
+                        let skip = if svn[1] > 0 { 2 } else { 0 };
+                        tdx.len() > skip && components_at_least(&svn[skip..], &tdx[skip..])
+                    }
+                    None => tdx.is_empty(),
+                };
+                components_at_least(&pck.tcb, &level.tcb.sgxtcbcomponents) && pck.pcesvn >= level.tcb.pcesvn && tdx_ok
+            })
+            .map(|level| level.tcb_status)
+            .ok_or(VerifyError::TcbLevelNotFound)?;
+        match &self.body.td_quote_body {
+            QuoteBodyKind::Tdx(body) => Ok(platform_status.max(tdx_module_status(body, tcb_info)?)),
+            QuoteBodyKind::Sgx(_) => Ok(platform_status),
+        }
+    }
+
+    /// Checks the QE report against `qe_identity` and returns the status of
+    /// the first TCB level its ISV SVN meets.
+    ///
+    /// The identity must be that of the TD QE, `"TD_QE"`, for a TD quote and
+    /// of the SGX QE, `"QE"`, for an enclave quote.
+    pub fn qe_identity_status(&self, qe_identity: &QeIdentity) -> Result<TcbStatus, VerifyError> {
+        let qe_report = &self.signature.qe_report;
+        let expected_id = match self.body.td_quote_body {
+            QuoteBodyKind::Tdx(_) => "TD_QE",
+            QuoteBodyKind::Sgx(_) => "QE",
+        };
+        if qe_identity.id != expected_id
+            || qe_report.mr_signer != qe_identity.mrsigner
+            || qe_report.isv_prod_id != qe_identity.isvprodid
+            || !masked_eq(&qe_report.misc_select.to_le_bytes(), &qe_identity.miscselect, &qe_identity.miscselect_mask)
+            || !masked_eq(&qe_report.attributes, &qe_identity.attributes, &qe_identity.attributes_mask)
+        {
+            return Err(VerifyError::QeIdentityMismatch);
+        }
+        qe_identity
+            .tcb_levels
+            .iter()
+            .find(|level| qe_report.isv_svn >= level.tcb.isvsvn)
+            .map(|level| level.tcb_status)
+            .ok_or(VerifyError::TcbLevelNotFound)
+    }
+}
+
+/// The PCS collateral needed to verify a quote with [`verify_quote`].
+#[derive(Debug, Clone)]
+pub struct Collateral {
+    /// PCS TCB info response, `{"tcbInfo": {...}, "signature": "..."}`.
+    pub tcb_info_json: String,
+    /// PCS QE identity response, `{"enclaveIdentity": {...}, "signature": "..."}`.
+    pub qe_identity_json: String,
+    /// PEM `TCB-Info-Issuer-Chain` of both responses, starting with the TCB
+    /// signing certificate.
+    pub tcb_signing_chain: Vec<u8>,
+    /// CRL of the PCK platform or processor CA, DER or PEM.
+    pub pck_crl: Vec<u8>,
+    /// CRL of the Intel SGX Root CA, DER or PEM.
+    pub root_crl: Vec<u8>,
+}
+
+/// The TCB info and QE identity of a [`Collateral`], with their signatures
+/// and validity periods checked.
+struct TrustedCollateral {
+    tcb_info: TcbInfo,
+    qe_identity: QeIdentity,
+}
+
+/// A PCS response split into the exact text of its signed object and the
+/// signature over it.
+#[derive(Deserialize)]
+struct SignedResponse<'a> {
+    #[serde(borrow, alias = "tcbInfo", alias = "enclaveIdentity")]
+    body: &'a RawValue,
+    signature: &'a str,
+}
+
+/// Parses a PCS date, `YYYY-MM-DDTHH:MM:SSZ`, as the time since the Unix epoch.
+fn parse_date(date: &str) -> Result<Duration, VerifyError> {
+    let date: DateTime = date.parse().map_err(|_| VerifyError::CollateralExpired)?;
+    Ok(date.unix_duration())
+}
+
+/// Checks the signature of a PCS response and parses its signed object.
+fn parse_signed<T: DeserializeOwned>(json: &str, key: &p256::ecdsa::VerifyingKey) -> Result<T, VerifyError> {
+    let invalid = |err| VerifyError::InvalidCollateral(ParseError::InvalidCollateral(err));
+    let response: SignedResponse<'_> = serde_json::from_str(json).map_err(invalid)?;
+    verify_collateral_signature(key, response.body.get().as_bytes(), response.signature)?;
+    serde_json::from_str(response.body.get()).map_err(invalid)
+}
+
+/// Checks the TCB info and QE identity signatures against the TCB signing
+/// chain anchored at `root_ca`, and that both are within their
+/// `issueDate`..`nextUpdate` period at `now`.
+fn verify_collateral(collateral: &Collateral, root_ca: &[u8], now: Duration) -> Result<TrustedCollateral, VerifyError> {
+    let key = tcb_signing_key(&collateral.tcb_signing_chain, root_ca, &collateral.root_crl, now)?;
+    let tcb_info: TcbInfo = parse_signed(&collateral.tcb_info_json, &key)?;
+    let qe_identity: QeIdentity = parse_signed(&collateral.qe_identity_json, &key)?;
+    for (issue_date, next_update) in [(&tcb_info.issue_date, &tcb_info.next_update), (&qe_identity.issue_date, &qe_identity.next_update)] {
+        if now < parse_date(issue_date)? || now > parse_date(next_update)? {
+            return Err(VerifyError::CollateralExpired);
+        }
+    }
+    Ok(TrustedCollateral { tcb_info, qe_identity })
+}
+
+fn now() -> Duration {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default()
+}
+
+/// A quote that passed every check of [`verify_quote`].
+#[derive(Debug, Clone)]
+pub struct VerifiedQuote {
+    quote: Quote,
+    tcb_status: TcbStatus,
+    qe_tcb_status: TcbStatus,
+}
+
+impl VerifiedQuote {
+    pub fn quote(&self) -> &Quote {
+        &self.quote
+    }
+
+    pub fn into_quote(self) -> Quote {
+        self.quote
+    }
+
+    /// The platform TCB status, which may still carry configuration or
+    /// software hardening advice.
+    pub fn tcb_status(&self) -> TcbStatus {
+        self.tcb_status
+    }
+
+    pub fn qe_tcb_status(&self) -> TcbStatus {
+        self.qe_tcb_status
+    }
+}
+
+/// Parses and fully verifies a quote against `collateral` at the current time.
+///
+/// The PCK certificate chain must lead to [`INTEL_SGX_ROOT_CA`] without a
+/// revoked certificate, the QE report must be signed by the PCK key, bind the
+/// attestation key and match the QE identity, the quote must be signed by the
+/// attestation key, and neither TCB status may be out of date or revoked.
+/// The TCB info and QE identity must be signed by the first certificate of
+/// the TCB signing chain, which must be issued by the root CA and not
+/// revoked, and must not be past their `nextUpdate`.
+pub fn verify_quote(quote_bytes: &[u8], collateral: &Collateral) -> Result<VerifiedQuote, VerifyError> {
+    verify_quote_with_root_ca(quote_bytes, collateral, INTEL_SGX_ROOT_CA)
+}
+
+/// [`verify_quote`] with the PCK certificate chain anchored at `root_ca`, a
+/// DER certificate, instead of [`INTEL_SGX_ROOT_CA`], for test and
+/// pre-production PKIs.
+pub fn verify_quote_with_root_ca(quote_bytes: &[u8], collateral: &Collateral, root_ca: &[u8]) -> Result<VerifiedQuote, VerifyError> {
+    let quote = parse_quote(quote_bytes).map_err(VerifyError::Parse)?;
+    quote.verify_cert_chain(root_ca)?;
+    quote.check_revocation_with_root_ca(&collateral.pck_crl, &collateral.root_crl, root_ca)?;
+    let trusted = verify_collateral(collateral, root_ca, now())?;
+    verify_after_chain(quote, &trusted)
+}
+
+/// Lazily runs [`verify_quote`] on each quote of `quotes`, with the same
+/// verdict for each.
+///
+/// The collateral signatures are checked once, the Intel root CA and both
+/// CRLs are decoded once, and the CRL and
+/// intermediate CA signatures are checked once per intermediate CA, so a long
+/// stream of quotes from a few platform CAs mostly costs its leaf and quote
+/// signatures. Chains of any other shape are checked as by [`verify_quote`].
+pub fn verify_stream<'a, I>(quotes: I, collateral: &'a Collateral) -> impl Iterator<Item = Result<VerifiedQuote, VerifyError>> + 'a
+where
+    I: Iterator<Item = Vec<u8>> + 'a,
+{
+    verify_stream_with_root_ca(quotes, collateral, INTEL_SGX_ROOT_CA)
+}
+
+/// [`verify_stream`] with the PCK certificate chains anchored at `root_ca`,
+/// as by [`verify_quote_with_root_ca`].
+pub fn verify_stream_with_root_ca<'a, I>(
+    quotes: I,
+    collateral: &'a Collateral,
+    root_ca: &'a [u8],
+) -> impl Iterator<Item = Result<VerifiedQuote, VerifyError>> + 'a
+where
+    I: Iterator<Item = Vec<u8>> + 'a,
+{
+    // Invalid CRLs or collateral leave these unset, so each quote reports the
+    // error in its usual place among the checks, as `verify_quote` would.
+    let mut verifier = ChainVerifier::new(root_ca, &collateral.pck_crl, &collateral.root_crl).ok();
+    let trusted = verify_collateral(collateral, root_ca, now()).ok();
+    quotes.map(move |quote_bytes| {
+        let quote = parse_quote(&quote_bytes).map_err(VerifyError::Parse)?;
+        match (&mut verifier, quote.certification_data().pck_cert_chain().and_then(Result::ok)) {
+            (Some(verifier), Some(chain)) if verifier.handles(&chain) => verifier.verify(&chain, now())?,
+            _ => {
+                quote.verify_cert_chain(root_ca)?;
+                quote.check_revocation_with_root_ca(&collateral.pck_crl, &collateral.root_crl, root_ca)?;
+            }
+        }
+        match &trusted {
+            Some(trusted) => verify_after_chain(quote, trusted),
+            None => verify_after_chain(quote, &verify_collateral(collateral, root_ca, now())?),
+        }
+    })
+}
+
+/// The checks of [`verify_quote`] that follow the PCK chain and revocation checks.
+fn verify_after_chain(quote: Quote, collateral: &TrustedCollateral) -> Result<VerifiedQuote, VerifyError> {
+    quote.verify_qe_report_signature()?;
+    quote.verify_qe_report_binding()?;
+    quote.verify_signature()?;
+
+    let qe_tcb_status = quote.qe_identity_status(&collateral.qe_identity)?;
+    let tcb_status = quote.tcb_status(&collateral.tcb_info)?;
+    for status in [qe_tcb_status, tcb_status] {
+        if !status.is_acceptable() {
+            return Err(VerifyError::TcbStatusRejected(status));
+        }
+    }
+    Ok(VerifiedQuote { quote, tcb_status, qe_tcb_status })
+}
@@ -0,0 +1,111 @@
+//! Field-by-field comparison of two quotes.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::{Quote, QuoteBodyKind, SgxQuoteBody, TDQuoteBody};
+
+/// A named field whose value differs between two quotes, rendered as hex for
+/// byte fields and in decimal or canonical form otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub field: &'static str,
+    pub left: String,
+    pub right: String,
+}
+
+struct Differ(Vec<FieldDiff>);
+
+impl Differ {
+    fn value<T: PartialEq + ToString>(&mut self, field: &'static str, left: T, right: T) {
+        if left != right {
+            self.0.push(FieldDiff { field, left: left.to_string(), right: right.to_string() });
+        }
+    }
+
+    fn bytes(&mut self, field: &'static str, left: &[u8], right: &[u8]) {
+        if left != right {
+            self.0.push(FieldDiff { field, left: hex::encode(left), right: hex::encode(right) });
+        }
+    }
+
+    fn opt_bytes<const N: usize>(&mut self, field: &'static str, left: &Option<[u8; N]>, right: &Option<[u8; N]>) {
+        if left != right {
+            let render = |value: &Option<[u8; N]>| value.as_ref().map_or_else(|| "none".to_string(), hex::encode);
+            self.0.push(FieldDiff { field, left: render(left), right: render(right) });
+        }
+    }
+
+    fn td(&mut self, left: &TDQuoteBody, right: &TDQuoteBody) {
+        self.bytes("tee_tcb_svn", &left.tee_tcb_svn, &right.tee_tcb_svn);
+        self.bytes("mrseam", &left.mrseam, &right.mrseam);
+        self.bytes("mrsignerseam", &left.mrsignerseam, &right.mrsignerseam);
+        self.bytes("seamattributes", &left.seamattributes, &right.seamattributes);
+        self.bytes("tdattributes", &left.tdattributes, &right.tdattributes);
+        self.bytes("xfam", &left.xfam, &right.xfam);
+        self.bytes("mrtd", &left.mrtd, &right.mrtd);
+        self.bytes("mrconfigid", &left.mrconfigid, &right.mrconfigid);
+        self.bytes("mrowner", &left.mrowner, &right.mrowner);
+        self.bytes("mrownerconfig", &left.mrownerconfig, &right.mrownerconfig);
+        self.bytes("rtmr0", &left.rtmr0, &right.rtmr0);
+        self.bytes("rtmr1", &left.rtmr1, &right.rtmr1);
+        self.bytes("rtmr2", &left.rtmr2, &right.rtmr2);
+        self.bytes("rtmr3", &left.rtmr3, &right.rtmr3);
+        self.bytes("reportdata", &left.reportdata, &right.reportdata);
+        self.opt_bytes("tee_tcb_svn_2", &left.tee_tcb_svn_2, &right.tee_tcb_svn_2);
+        self.opt_bytes("mrservicetd", &left.mrservicetd, &right.mrservicetd);
+    }
+
+    fn sgx(&mut self, left: &SgxQuoteBody, right: &SgxQuoteBody) {
+        self.bytes("cpu_svn", &left.cpu_svn, &right.cpu_svn);
+        self.value("misc_select", left.misc_select, right.misc_select);
+        self.bytes("attributes", &left.attributes, &right.attributes);
+        self.bytes("mr_enclave", &left.mr_enclave, &right.mr_enclave);
+        self.bytes("mr_signer", &left.mr_signer, &right.mr_signer);
+        self.value("isv_prod_id", left.isv_prod_id, right.isv_prod_id);
+        self.value("isv_svn", left.isv_svn, right.isv_svn);
+        self.bytes("report_data", &left.report_data, &right.report_data);
+    }
+}
+
+impl Quote {
+    /// Lists the header and report body fields that differ from `other`, in
+    /// layout order. The signature section is not compared, since it differs
+    /// between any two quotes.
+    ///
+    /// If the two bodies are of different kinds only `td_quote_body_type` and
+    /// `size` are reported for the body.
+    pub fn diff(&self, other: &Quote) -> Vec<FieldDiff> {
+        let mut differ = Differ(Vec::new());
+        let (left, right) = (&self.header, &other.header);
+        differ.value("version", left.version, right.version);
+        differ.value("attestation_key_type", left.attestation_key_type, right.attestation_key_type);
+        differ.value("tee_type", format!("{:?}", left.tee_type), format!("{:?}", right.tee_type));
+        differ.bytes("reserved1", &left.reserved1, &right.reserved1);
+        differ.bytes("reserved2", &left.reserved2, &right.reserved2);
+        differ.value("qe_vendor_id", left.qe_vendor_id, right.qe_vendor_id);
+        differ.bytes("user_data", &left.user_data, &right.user_data);
+
+        let (left, right) = (&self.body, &other.body);
+        differ.value("td_quote_body_type", left.td_quote_body_type, right.td_quote_body_type);
+        differ.value("size", left.size, right.size);
+        match (&left.td_quote_body, &right.td_quote_body) {
+            (QuoteBodyKind::Tdx(left), QuoteBodyKind::Tdx(right)) => differ.td(left, right),
+            (QuoteBodyKind::Sgx(left), QuoteBodyKind::Sgx(right)) => differ.sgx(left, right),
+            _ => {}
+        }
+        differ.0
+    }
+
+    /// Index of the first RTMR that differs from `other`, or `None` if all four
+    /// match. A TD quote compared with an SGX quote, which has no RTMRs,
+    /// differs at index 0; two SGX quotes never differ.
+    pub fn rtmr_diff(&self, other: &Quote) -> Option<usize> {
+        match (self.body.td_quote_body.as_tdx(), other.body.td_quote_body.as_tdx()) {
+            (Some(left), Some(right)) => left.rtmrs().iter().zip(right.rtmrs().iter()).position(|(l, r)| l != r),
+            (None, None) => None,
+            _ => Some(0),
+        }
+    }
+}
@@ -0,0 +1,299 @@
+//! Human-readable dumps of the parsed structures, as printed by `parserV5`.
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::{CertificationData, PckId, Quote, QuoteSignatureData};
+use crate::{AttestationKeyType, QuoteBody, QuoteBodyKind, QuoteBodyType, QuoteHeader, SgxQuoteBody, TDQuoteBody};
+
+/// How byte fields are rendered in the human-readable dumps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HexFormat {
+    /// Lowercase hex, e.g. `18bcec`.
+    #[default]
+    Plain,
+    /// Lowercase hex after a `0x` prefix, e.g. `0x18bcec`.
+    Prefixed,
+    /// Colon-separated bytes, e.g. `18:bc:ec`.
+    Grouped,
+}
+
+/// Formats a byte slice as lowercase hex without allocating.
+pub(crate) struct Hex<'a>(pub(crate) &'a [u8], pub(crate) HexFormat);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.1 == HexFormat::Prefixed {
+            write!(f, "0x")?;
+        }
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 && self.1 == HexFormat::Grouped {
+                write!(f, ":")?;
+            }
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Same as `Display`, so byte fields read as hex in `{:?}` output.
+impl fmt::Debug for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Debug-formats a list of byte strings as a list of [`Hex`] values.
+#[cfg(feature = "alloc")]
+pub(crate) struct HexList<'a, T>(pub(crate) &'a [T]);
+
+#[cfg(feature = "alloc")]
+impl<T: AsRef<[u8]>> fmt::Debug for HexList<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.iter().map(|bytes| Hex(bytes.as_ref(), HexFormat::Plain))).finish()
+    }
+}
+
+/// A dump that can be rendered with any [`HexFormat`]; `Display` uses
+/// [`HexFormat::Plain`].
+trait Dump {
+    fn dump(&self, f: &mut fmt::Formatter<'_>, hex: HexFormat) -> fmt::Result;
+}
+
+macro_rules! display_via_dump {
+    ($($ty:ty),*) => {
+        $(impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.dump(f, HexFormat::Plain)
+            }
+        })*
+    };
+}
+
+display_via_dump!(QuoteHeader, TDQuoteBody, SgxQuoteBody, QuoteBodyKind, QuoteBody);
+#[cfg(feature = "alloc")]
+display_via_dump!(QuoteSignatureData, Quote);
+
+/// Renders a value's dump with a chosen [`HexFormat`].
+#[cfg(feature = "alloc")]
+struct WithHexFormat<'a, T>(&'a T, HexFormat);
+
+#[cfg(feature = "alloc")]
+impl<T: Dump> fmt::Display for WithHexFormat<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.dump(f, self.1)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Quote {
+    /// Same dump as the `Display` impl with byte fields rendered as `hex`.
+    pub fn display_with(&self, hex: HexFormat) -> impl fmt::Display + '_ {
+        WithHexFormat(self, hex)
+    }
+}
+
+impl Dump for QuoteHeader {
+    fn dump(&self, f: &mut fmt::Formatter<'_>, hex: HexFormat) -> fmt::Result {
+        writeln!(f, "Quote Header:")?;
+        writeln!(f, "  Version: {}", self.version)?;
+        writeln!(f, "  Attestation Key Type: {}", self.attestation_key_type)?;
+        writeln!(f, "  TEE Type: {:?}", self.tee_type)?;
+        writeln!(f, "  Reserved 1: {}", Hex(&self.reserved1, hex))?;
+        writeln!(f, "  Reserved 2: {}", Hex(&self.reserved2, hex))?;
+        writeln!(f, "  QE Vendor ID: {}", self.qe_vendor_id)?;
+        write!(f, "  User Data: {}", Hex(&self.user_data, hex))
+    }
+}
+
+impl Dump for TDQuoteBody {
+    fn dump(&self, f: &mut fmt::Formatter<'_>, hex: HexFormat) -> fmt::Result {
+        writeln!(f, "  TEE TCB SVN: {}", Hex(&self.tee_tcb_svn, hex))?;
+        writeln!(f, "  MRSEAM: {}", Hex(&self.mrseam, hex))?;
+        writeln!(f, "  MRSIGNERSEAM: {}", Hex(&self.mrsignerseam, hex))?;
+        writeln!(f, "  Seam Attributes: {}", Hex(&self.seamattributes, hex))?;
+        writeln!(f, "  TD Attributes: {}", Hex(&self.tdattributes, hex))?;
+        writeln!(f, "  \t{}", self.tdattributes_decoded())?;
+        writeln!(f, "  XFAM: {}", Hex(&self.xfam, hex))?;
+        writeln!(f, "  MRTD: {}", Hex(&self.mrtd, hex))?;
+        writeln!(f, "  MRCONFIGID: {}", Hex(&self.mrconfigid, hex))?;
+        writeln!(f, "  MROWNER: {}", Hex(&self.mrowner, hex))?;
+        writeln!(f, "  MROWNERCONFIG: {}", Hex(&self.mrownerconfig, hex))?;
+        writeln!(f, "  RTMR0: {}", Hex(&self.rtmr0, hex))?;
+        writeln!(f, "  RTMR1: {}", Hex(&self.rtmr1, hex))?;
+        writeln!(f, "  RTMR2: {}", Hex(&self.rtmr2, hex))?;
+        writeln!(f, "  RTMR3: {}", Hex(&self.rtmr3, hex))?;
+        write!(f, "  Report Data: {}", Hex(&self.reportdata, hex))?;
+        if let Some(tee_tcb_svn_2) = &self.tee_tcb_svn_2 {
+            write!(f, "\n  TEE TCB SVN 2: {}", Hex(tee_tcb_svn_2, hex))?;
+        }
+        if let Some(mrservicetd) = &self.mrservicetd {
+            write!(f, "\n  MRSERVICETD: {}", Hex(mrservicetd, hex))?;
+        }
+        Ok(())
+    }
+}
+
+impl Dump for SgxQuoteBody {
+    fn dump(&self, f: &mut fmt::Formatter<'_>, hex: HexFormat) -> fmt::Result {
+        writeln!(f, "  CPU SVN: {}", Hex(&self.cpu_svn, hex))?;
+        writeln!(f, "  MiscSelect: {:08x}", self.misc_select)?;
+        writeln!(f, "  Attributes: {}", Hex(&self.attributes, hex))?;
+        writeln!(f, "  MRENCLAVE: {}", Hex(&self.mr_enclave, hex))?;
+        writeln!(f, "  MRSIGNER: {}", Hex(&self.mr_signer, hex))?;
+        writeln!(f, "  ISV ProdID: {}", self.isv_prod_id)?;
+        writeln!(f, "  ISV SVN: {}", self.isv_svn)?;
+        write!(f, "  Report Data: {}", Hex(&self.report_data, hex))
+    }
+}
+
+impl Dump for QuoteBodyKind {
+    fn dump(&self, f: &mut fmt::Formatter<'_>, hex: HexFormat) -> fmt::Result {
+        match self {
+            QuoteBodyKind::Sgx(body) => body.dump(f, hex),
+            QuoteBodyKind::Tdx(body) => body.dump(f, hex),
+        }
+    }
+}
+
+impl fmt::Display for AttestationKeyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AttestationKeyType::EcdsaP256 => "ECDSA-256-with-P-256",
+            AttestationKeyType::EcdsaP384 => "ECDSA-384-with-P-384",
+            AttestationKeyType::Unknown(_) => "unknown",
+        };
+        write!(f, "{} ({})", u16::from(*self), name)
+    }
+}
+
+impl fmt::Display for QuoteBodyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            QuoteBodyType::SgxReport => "SGX report",
+            QuoteBodyType::Tdx10 => "TDX 1.0",
+            QuoteBodyType::Tdx15 => "TDX 1.5",
+            QuoteBodyType::Unknown(_) => "unknown",
+        };
+        write!(f, "{} ({})", u16::from(*self), name)
+    }
+}
+
+impl Dump for QuoteBody {
+    fn dump(&self, f: &mut fmt::Formatter<'_>, hex: HexFormat) -> fmt::Result {
+        writeln!(f, "Quote Body:")?;
+        writeln!(f, "  TD Quote Body Type: {}", self.td_quote_body_type)?;
+        writeln!(f, "  Size: {}", self.size)?;
+        self.td_quote_body.dump(f, hex)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for CertificationData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Certification Data:")?;
+        writeln!(f, "  Type: {}", self.cert_data_type)?;
+        write!(f, "  Size: {}", self.size)?;
+        match self.pck_cert_chain() {
+            Some(Ok(chain)) => write!(f, "\n  PCK Certificates: {}", chain.len()),
+            Some(Err(err)) => write!(f, "\n  PCK Certificates: {}", err),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Dump for QuoteSignatureData {
+    fn dump(&self, f: &mut fmt::Formatter<'_>, hex: HexFormat) -> fmt::Result {
+        writeln!(f, "Quote Signature Data:")?;
+        writeln!(f, "  Signature Data Length: {}", self.signature_data_len)?;
+        writeln!(f, "  Signature: {}", Hex(&self.signature, hex))?;
+        writeln!(f, "  Attestation Key: {}", Hex(&self.attestation_key, hex))?;
+        writeln!(f, "  QE Certification Data Type: {}", self.qe_cert_data_type)?;
+        writeln!(f, "  QE Certification Data Size: {}", self.qe_cert_data_size)?;
+        writeln!(f, "QE Report:")?;
+        self.qe_report.dump(f, hex)?;
+        writeln!(f)?;
+        writeln!(f, "  QE Report Signature: {}", Hex(&self.qe_report_signature, hex))?;
+        writeln!(f, "  QE Auth Data: {}", Hex(&self.qe_auth_data, hex))?;
+        write!(f, "{}", self.certification_data)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Dump for Quote {
+    fn dump(&self, f: &mut fmt::Formatter<'_>, hex: HexFormat) -> fmt::Result {
+        self.header.dump(f, hex)?;
+        writeln!(f)?;
+        self.body.dump(f, hex)?;
+        writeln!(f)?;
+        self.signature.dump(f, hex)
+    }
+}
+
+hex_debug!(QuoteHeader {
+    version,
+    attestation_key_type,
+    tee_type,
+    reserved1: hex,
+    reserved2: hex,
+    qe_vendor_id,
+    user_data: hex,
+});
+
+hex_debug!(TDQuoteBody {
+    tee_tcb_svn: hex,
+    mrseam: hex,
+    mrsignerseam: hex,
+    seamattributes: hex,
+    tdattributes: hex,
+    xfam: hex,
+    mrtd: hex,
+    mrconfigid: hex,
+    mrowner: hex,
+    mrownerconfig: hex,
+    rtmr0: hex,
+    rtmr1: hex,
+    rtmr2: hex,
+    rtmr3: hex,
+    reportdata: hex,
+    tee_tcb_svn_2: opt_hex,
+    mrservicetd: opt_hex,
+});
+
+hex_debug!(SgxQuoteBody {
+    cpu_svn: hex,
+    misc_select,
+    reserved1: hex,
+    attributes: hex,
+    mr_enclave: hex,
+    reserved2: hex,
+    mr_signer: hex,
+    reserved3: hex,
+    isv_prod_id,
+    isv_svn,
+    reserved4: hex,
+    report_data: hex,
+});
+
+#[cfg(feature = "alloc")]
+hex_debug!(QuoteSignatureData {
+    signature_data_len,
+    signature: hex,
+    attestation_key: hex,
+    qe_cert_data_type,
+    qe_cert_data_size,
+    qe_report,
+    qe_report_signature: hex,
+    qe_auth_data: hex,
+    certification_data,
+});
+
+#[cfg(feature = "alloc")]
+hex_debug!(Quote { header, body, signature, signed_data: hex });
+
+#[cfg(feature = "alloc")]
+hex_debug!(CertificationData { cert_data_type, size, data: hex });
+
+#[cfg(feature = "alloc")]
+hex_debug!(PckId { ppid: hex, cpusvn: hex, pcesvn, pceid: hex });
@@ -0,0 +1,164 @@
+//! Re-encoding of parsed quotes into their little-endian wire format.
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use crate::{
+    CertificationData, Quote, QuoteBody, QuoteBodyKind, QuoteHeader, QuoteSignatureData, SgxQuoteBody, SgxQuoteV3Header,
+    TDQuoteBody,
+};
+
+impl QuoteHeader {
+    pub(crate) fn encode_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&u16::from(self.attestation_key_type).to_le_bytes());
+        out.extend_from_slice(&u32::from(self.tee_type).to_le_bytes());
+        out.extend_from_slice(&self.reserved1);
+        out.extend_from_slice(&self.reserved2);
+        out.extend_from_slice(self.qe_vendor_id.as_bytes());
+        out.extend_from_slice(&self.user_data);
+    }
+}
+
+impl SgxQuoteV3Header {
+    /// Re-encodes `signed_data` for a deserialized [`SgxQuoteV3`](crate::SgxQuoteV3).
+    #[cfg(feature = "serde")]
+    pub(crate) fn encode_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&u16::from(self.attestation_key_type).to_le_bytes());
+        out.extend_from_slice(&self.reserved);
+        out.extend_from_slice(&self.qe_svn.to_le_bytes());
+        out.extend_from_slice(&self.pce_svn.to_le_bytes());
+        out.extend_from_slice(self.qe_vendor_id.as_bytes());
+        out.extend_from_slice(&self.user_data);
+    }
+}
+
+impl TDQuoteBody {
+    pub(crate) fn encode_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.tee_tcb_svn);
+        out.extend_from_slice(&self.mrseam);
+        out.extend_from_slice(&self.mrsignerseam);
+        out.extend_from_slice(&self.seamattributes);
+        out.extend_from_slice(&self.tdattributes);
+        out.extend_from_slice(&self.xfam);
+        out.extend_from_slice(&self.mrtd);
+        out.extend_from_slice(&self.mrconfigid);
+        out.extend_from_slice(&self.mrowner);
+        out.extend_from_slice(&self.mrownerconfig);
+        out.extend_from_slice(&self.rtmr0);
+        out.extend_from_slice(&self.rtmr1);
+        out.extend_from_slice(&self.rtmr2);
+        out.extend_from_slice(&self.rtmr3);
+        out.extend_from_slice(&self.reportdata);
+        if let Some(tee_tcb_svn_2) = &self.tee_tcb_svn_2 {
+            out.extend_from_slice(tee_tcb_svn_2);
+        }
+        if let Some(mrservicetd) = &self.mrservicetd {
+            out.extend_from_slice(mrservicetd);
+        }
+    }
+}
+
+impl SgxQuoteBody {
+    pub(crate) fn encode_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.cpu_svn);
+        out.extend_from_slice(&self.misc_select.to_le_bytes());
+        out.extend_from_slice(&self.reserved1);
+        out.extend_from_slice(&self.attributes);
+        out.extend_from_slice(&self.mr_enclave);
+        out.extend_from_slice(&self.reserved2);
+        out.extend_from_slice(&self.mr_signer);
+        out.extend_from_slice(&self.reserved3);
+        out.extend_from_slice(&self.isv_prod_id.to_le_bytes());
+        out.extend_from_slice(&self.isv_svn.to_le_bytes());
+        out.extend_from_slice(&self.reserved4);
+        out.extend_from_slice(&self.report_data);
+    }
+}
+
+impl QuoteBodyKind {
+    pub(crate) fn encode_into(&self, out: &mut Vec<u8>) {
+        match self {
+            QuoteBodyKind::Sgx(body) => body.encode_into(out),
+            QuoteBodyKind::Tdx(body) => body.encode_into(out),
+        }
+    }
+}
+
+impl QuoteBody {
+    /// Encodes the body in its v5 form, prefixed with the type and size.
+    pub(crate) fn encode_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&u16::from(self.td_quote_body_type).to_le_bytes());
+        out.extend_from_slice(&self.size.to_le_bytes());
+        self.td_quote_body.encode_into(out);
+    }
+}
+
+impl CertificationData {
+    pub(crate) fn encode_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.cert_data_type.to_le_bytes());
+        out.extend_from_slice(&self.size.to_le_bytes());
+        out.extend_from_slice(&self.data);
+    }
+}
+
+impl QuoteSignatureData {
+    pub(crate) fn encode_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.signature_data_len.to_le_bytes());
+        out.extend_from_slice(&self.signature);
+        out.extend_from_slice(&self.attestation_key);
+        out.extend_from_slice(&self.qe_cert_data_type.to_le_bytes());
+        out.extend_from_slice(&self.qe_cert_data_size.to_le_bytes());
+        self.qe_report.encode_into(out);
+        out.extend_from_slice(&self.qe_report_signature);
+        out.extend_from_slice(&(self.qe_auth_data.len() as u16).to_le_bytes());
+        out.extend_from_slice(&self.qe_auth_data);
+        self.certification_data.encode_into(out);
+    }
+}
+
+/// Encodes the header and body the way the quote signature covers them.
+pub(crate) fn encode_signed_data(header: &QuoteHeader, body: &QuoteBody) -> Vec<u8> {
+    let mut out = Vec::new();
+    header.encode_into(&mut out);
+    if header.version == 4 {
+        // v4 quotes carry the body without a type and size descriptor.
+        body.td_quote_body.encode_into(&mut out);
+    } else {
+        body.encode_into(&mut out);
+    }
+    out
+}
+
+impl Quote {
+    pub(crate) fn encode_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&encode_signed_data(&self.header, &self.body));
+        self.signature.encode_into(out);
+    }
+
+    /// Serializes the quote back into the exact bytes it was parsed from.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.encode_into(&mut bytes);
+        bytes
+    }
+}
+
+/// Adds `write_to`, writing what `encode_into` produces, to each type.
+#[cfg(feature = "std")]
+macro_rules! write_to {
+    ($($ty:ty),*) => {
+        $(impl $ty {
+            pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+                let mut bytes = Vec::new();
+                self.encode_into(&mut bytes);
+                w.write_all(&bytes)
+            }
+        })*
+    };
+}
+
+#[cfg(feature = "std")]
+write_to!(QuoteHeader, TDQuoteBody, SgxQuoteBody, QuoteBodyKind, QuoteBody, CertificationData, QuoteSignatureData, Quote);
@@ -0,0 +1,113 @@
+//! Parsing and replay of the CCEL event log that accompanies a TD quote.
+//!
+//! The log uses the TCG crypto-agile format: a SHA-1 style header event
+//! carrying the spec ID, followed by `TCG_PCR_EVENT2` records. The "PCR" index
+//! of each record is a CC measurement register index, where 0 is MRTD and 1-4
+//! are RTMR0-3.
+
+use alloc::vec::Vec;
+use sha2::{Digest, Sha384};
+
+use crate::{ParseError, Reader};
+
+/// TCG algorithm ID of SHA-384, the hash RTMRs are extended with.
+const TPM_ALG_SHA384: u16 = 0x000c;
+/// Length of the SHA-1 digest in the header event.
+const SHA1_DIGEST_LEN: usize = 20;
+/// Index value that marks the unused, 0xff-filled tail of the log area.
+const LOG_END: u32 = 0xffff_ffff;
+
+/// One measurement extended into an RTMR.
+#[derive(Clone)]
+pub struct RtmrEvent {
+    /// RTMR the event was extended into, 0-3.
+    pub rtmr_index: u32,
+    pub event_type: u32,
+    /// SHA-384 digest that was extended into the RTMR.
+    pub digest: [u8; 48],
+    pub event_data: Vec<u8>,
+}
+
+hex_debug!(RtmrEvent { rtmr_index, event_type, digest: hex, event_data: hex });
+
+/// Reads the digest sizes advertised by the `Spec ID Event03` header event.
+fn parse_spec_id_event(reader: &mut Reader<'_>) -> Result<Vec<(u16, usize)>, ParseError> {
+    let _signature: [u8; 16] = reader.read_bytes("signature")?;
+    let _platform_class = reader.read_u32("platform_class")?;
+    let _spec_version: [u8; 4] = reader.read_bytes("spec_version")?;
+    let number_of_algorithms = reader.read_u32("number_of_algorithms")?;
+    let mut digest_sizes = Vec::new();
+    for _ in 0..number_of_algorithms {
+        let algorithm_id = reader.read_u16("algorithm_id")?;
+        let digest_size = reader.read_u16("digest_size")?;
+        digest_sizes.push((algorithm_id, digest_size as usize));
+    }
+    Ok(digest_sizes)
+}
+
+/// Parses a CCEL event log into the events extended into RTMR0-3.
+///
+/// Events extended into MRTD are not RTMR events and are skipped. Parsing
+/// stops at the end of the buffer or at the 0xff padding that fills the rest
+/// of the log area.
+pub fn parse_ccel(data: &[u8]) -> Result<Vec<RtmrEvent>, ParseError> {
+    let mut reader = Reader::new(data);
+
+    let _pcr_index = reader.read_u32("pcr_index")?;
+    let _event_type = reader.read_u32("event_type")?;
+    let _digest = reader.read_slice(SHA1_DIGEST_LEN, "digest")?;
+    let spec_id_size = reader.read_u32("spec_id_size")?;
+    let digest_sizes = parse_spec_id_event(&mut reader.sub_reader(spec_id_size as usize, "spec_id_event")?)?;
+
+    let mut events = Vec::new();
+    while reader.remaining() >= 8 {
+        let mr_index = reader.read_u32("mr_index")?;
+        if mr_index == LOG_END {
+            break;
+        }
+        let event_type = reader.read_u32("event_type")?;
+
+        let mut digest = None;
+        let digest_count = reader.read_u32("digest_count")?;
+        for _ in 0..digest_count {
+            let algorithm_id = reader.read_u16("algorithm_id")?;
+            let size = digest_sizes
+                .iter()
+                .find(|(id, _)| *id == algorithm_id)
+                .map(|(_, size)| *size)
+                .ok_or(ParseError::InvalidEventLog)?;
+            let bytes = reader.read_slice(size, "digest")?;
+            if algorithm_id == TPM_ALG_SHA384 {
+                digest = Some(bytes.try_into().map_err(|_| ParseError::InvalidEventLog)?);
+            }
+        }
+        let event_size = reader.read_u32("event_size")?;
+        let event_data = reader.read_slice(event_size as usize, "event_data")?.to_vec();
+
+        if mr_index == 0 {
+            continue;
+        }
+        if mr_index > 4 {
+            return Err(ParseError::InvalidEventLog);
+        }
+        events.push(RtmrEvent {
+            rtmr_index: mr_index - 1,
+            event_type,
+            digest: digest.ok_or(ParseError::InvalidEventLog)?,
+            event_data,
+        });
+    }
+    Ok(events)
+}
+
+/// Recomputes RTMR `index` by extending, from zero, every event logged to it.
+pub fn replay_rtmr(events: &[RtmrEvent], index: u32) -> [u8; 48] {
+    let mut rtmr = [0u8; 48];
+    for event in events.iter().filter(|event| event.rtmr_index == index) {
+        let mut hasher = Sha384::new();
+        hasher.update(rtmr);
+        hasher.update(event.digest);
+        rtmr.copy_from_slice(&hasher.finalize());
+    }
+    rtmr
+}
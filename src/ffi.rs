@@ -0,0 +1,119 @@
+//! C ABI for linking the parser from C, C++ or Go.
+//!
+//! No memory crosses the boundary in either direction: the caller owns the
+//! input buffer and the output struct, and the library owns the error string.
+
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+use crate::{parse_quote, QuoteBodyKind};
+
+/// Flattened header and TD report body fields of a parsed quote.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct TdxQuoteView {
+    pub version: u16,
+    pub attestation_key_type: u16,
+    pub tee_type: u32,
+    pub td_quote_body_type: u16,
+    pub tee_tcb_svn: [u8; 16],
+    pub mrseam: [u8; 48],
+    pub mrsignerseam: [u8; 48],
+    pub seamattributes: [u8; 8],
+    pub tdattributes: [u8; 8],
+    pub xfam: [u8; 8],
+    pub mrtd: [u8; 48],
+    pub mrconfigid: [u8; 48],
+    pub mrowner: [u8; 48],
+    pub mrownerconfig: [u8; 48],
+    pub rtmr0: [u8; 48],
+    pub rtmr1: [u8; 48],
+    pub rtmr2: [u8; 48],
+    pub rtmr3: [u8; 48],
+    pub reportdata: [u8; 64],
+}
+
+/// Returned by [`tdx_parse_quote`] on success.
+pub const TDX_OK: c_int = 0;
+/// A pointer argument was null.
+pub const TDX_ERR_NULL_POINTER: c_int = -1;
+/// The quote could not be parsed.
+pub const TDX_ERR_PARSE: c_int = -2;
+/// The quote parsed but does not carry a TD report body.
+pub const TDX_ERR_NOT_TDX: c_int = -3;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Parses the `len` bytes at `data` and fills `*out` with the TD measurements.
+///
+/// Returns [`TDX_OK`] or a negative `TDX_ERR_*` code, in which case
+/// [`tdx_last_error`] describes the failure and `*out` is left untouched.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes and `out` to a writable
+/// `TdxQuoteView`. Both stay owned by the caller and are not retained after
+/// the call returns.
+#[no_mangle]
+pub unsafe extern "C" fn tdx_parse_quote(data: *const u8, len: usize, out: *mut TdxQuoteView) -> c_int {
+    if data.is_null() || out.is_null() {
+        set_last_error("null pointer argument".to_string());
+        return TDX_ERR_NULL_POINTER;
+    }
+    let bytes = std::slice::from_raw_parts(data, len);
+    let quote = match parse_quote(bytes) {
+        Ok(quote) => quote,
+        Err(err) => {
+            set_last_error(err.to_string());
+            return TDX_ERR_PARSE;
+        }
+    };
+    let body = match &quote.body.td_quote_body {
+        QuoteBodyKind::Tdx(body) => body,
+        QuoteBodyKind::Sgx(_) => {
+            set_last_error("quote does not carry a TD report body".to_string());
+            return TDX_ERR_NOT_TDX;
+        }
+    };
+    out.write(TdxQuoteView {
+        version: quote.header.version,
+        attestation_key_type: u16::from(quote.header.attestation_key_type),
+        tee_type: u32::from(quote.header.tee_type),
+        td_quote_body_type: u16::from(quote.body.td_quote_body_type),
+        tee_tcb_svn: body.tee_tcb_svn,
+        mrseam: body.mrseam,
+        mrsignerseam: body.mrsignerseam,
+        seamattributes: body.seamattributes,
+        tdattributes: body.tdattributes,
+        xfam: body.xfam,
+        mrtd: body.mrtd,
+        mrconfigid: body.mrconfigid,
+        mrowner: body.mrowner,
+        mrownerconfig: body.mrownerconfig,
+        rtmr0: body.rtmr0,
+        rtmr1: body.rtmr1,
+        rtmr2: body.rtmr2,
+        rtmr3: body.rtmr3,
+        reportdata: body.reportdata,
+    });
+    TDX_OK
+}
+
+/// Returns the message of the last failed call on this thread, or null if
+/// none has failed yet.
+///
+/// The string is owned by the library and must not be freed. It stays valid
+/// until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn tdx_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
@@ -0,0 +1,38 @@
+//! Nonce-independent identity of the measured TD or enclave.
+
+use sha2::{Digest, Sha256};
+
+use crate::{Quote, QuoteBodyKind};
+
+impl Quote {
+    /// SHA-256 over the measurement fields that stay fixed across boots of
+    /// the same TD, so that quotes can be grouped regardless of report data,
+    /// RTMRs and signatures.
+    ///
+    /// For TD bodies this hashes `mrtd`, `mrseam`, `mrsignerseam`,
+    /// `mrconfigid`, `mrowner`, `mrownerconfig`, `xfam` and `tdattributes`,
+    /// in that order. For SGX report bodies it hashes `mr_enclave`,
+    /// `mr_signer`, `attributes` and `misc_select`.
+    pub fn measurement_fingerprint(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        match &self.body.td_quote_body {
+            QuoteBodyKind::Tdx(body) => {
+                hasher.update(body.mrtd);
+                hasher.update(body.mrseam);
+                hasher.update(body.mrsignerseam);
+                hasher.update(body.mrconfigid);
+                hasher.update(body.mrowner);
+                hasher.update(body.mrownerconfig);
+                hasher.update(body.xfam);
+                hasher.update(body.tdattributes);
+            }
+            QuoteBodyKind::Sgx(body) => {
+                hasher.update(body.mr_enclave);
+                hasher.update(body.mr_signer);
+                hasher.update(body.attributes);
+                hasher.update(body.misc_select.to_le_bytes());
+            }
+        }
+        hasher.finalize().into()
+    }
+}
@@ -0,0 +1,36 @@
+//! Transparent decompression of gzip-compressed quote files.
+
+use std::borrow::Cow;
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+
+use crate::{parse_quote, ParseError, Quote};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Largest decompressed quote [`gunzip_if_compressed`] accepts, far above
+/// the few kilobytes of a quote with its PCK certificate chain.
+pub const MAX_QUOTE_LEN: usize = 1 << 20;
+
+/// Decompresses `data` if it starts with the gzip magic bytes and returns it
+/// unchanged otherwise. No quote version starts with these bytes.
+///
+/// Fails with [`ParseError::DecompressedTooLarge`] rather than decompress
+/// more than [`MAX_QUOTE_LEN`] bytes.
+pub fn gunzip_if_compressed(data: &[u8]) -> Result<Cow<'_, [u8]>, ParseError> {
+    if !data.starts_with(&GZIP_MAGIC) {
+        return Ok(Cow::Borrowed(data));
+    }
+    let mut decompressed = Vec::new();
+    GzDecoder::new(data).take(MAX_QUOTE_LEN as u64 + 1).read_to_end(&mut decompressed)?;
+    if decompressed.len() > MAX_QUOTE_LEN {
+        return Err(ParseError::DecompressedTooLarge { limit: MAX_QUOTE_LEN });
+    }
+    Ok(Cow::Owned(decompressed))
+}
+
+/// Parses a quote that may be gzip-compressed, as in `.bin.gz` archives.
+pub fn parse_maybe_gzip(data: &[u8]) -> Result<Quote, ParseError> {
+    parse_quote(&gunzip_if_compressed(data)?)
+}
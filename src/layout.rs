@@ -0,0 +1,67 @@
+//! Sizes of the quote structures and their fields, and the offsets of the TD
+//! report body fields.
+//!
+//! Body offsets are relative to the start of the body, which follows the
+//! header directly in a v4 quote and the body descriptor in a v5 quote.
+
+/// The quote header.
+pub const HEADER_LEN: usize = 48;
+/// The v5 body descriptor: a `u16` body type and a `u32` body size.
+pub const BODY_DESCRIPTOR_LEN: usize = 6;
+/// The `u32` length in front of the signature section.
+pub const SIGNATURE_DATA_LEN_LEN: usize = 4;
+
+pub const QE_VENDOR_ID_LEN: usize = 16;
+pub const USER_DATA_LEN: usize = 20;
+/// The leading bytes of `user_data` where Intel's QE places the platform identifier.
+pub const PLATFORM_ID_LEN: usize = 16;
+
+pub const TEE_TCB_SVN_LEN: usize = 16;
+pub const MRSEAM_LEN: usize = 48;
+pub const MRSIGNERSEAM_LEN: usize = 48;
+pub const SEAM_ATTRIBUTES_LEN: usize = 8;
+pub const TD_ATTRIBUTES_LEN: usize = 8;
+pub const XFAM_LEN: usize = 8;
+pub const MRTD_LEN: usize = 48;
+pub const MRCONFIGID_LEN: usize = 48;
+pub const MROWNER_LEN: usize = 48;
+pub const MROWNERCONFIG_LEN: usize = 48;
+/// Each of `rtmr0` to `rtmr3`.
+pub const RTMR_LEN: usize = 48;
+/// `reportdata` of a TD body and `report_data` of an SGX report.
+pub const REPORT_DATA_LEN: usize = 64;
+pub const MRSERVICETD_LEN: usize = 48;
+
+pub const TD_TEE_TCB_SVN_OFFSET: usize = 0;
+pub const TD_MRSEAM_OFFSET: usize = TD_TEE_TCB_SVN_OFFSET + TEE_TCB_SVN_LEN;
+pub const TD_MRSIGNERSEAM_OFFSET: usize = TD_MRSEAM_OFFSET + MRSEAM_LEN;
+pub const TD_SEAM_ATTRIBUTES_OFFSET: usize = TD_MRSIGNERSEAM_OFFSET + MRSIGNERSEAM_LEN;
+pub const TD_ATTRIBUTES_OFFSET: usize = TD_SEAM_ATTRIBUTES_OFFSET + SEAM_ATTRIBUTES_LEN;
+pub const TD_XFAM_OFFSET: usize = TD_ATTRIBUTES_OFFSET + TD_ATTRIBUTES_LEN;
+pub const TD_MRTD_OFFSET: usize = TD_XFAM_OFFSET + XFAM_LEN;
+pub const TD_MRCONFIGID_OFFSET: usize = TD_MRTD_OFFSET + MRTD_LEN;
+pub const TD_MROWNER_OFFSET: usize = TD_MRCONFIGID_OFFSET + MRCONFIGID_LEN;
+pub const TD_MROWNERCONFIG_OFFSET: usize = TD_MROWNER_OFFSET + MROWNER_LEN;
+/// Offset of `rtmr0`; RTMR `i` starts `i * RTMR_LEN` bytes later.
+pub const TD_RTMR_OFFSET: usize = TD_MROWNERCONFIG_OFFSET + MROWNERCONFIG_LEN;
+pub const TD_REPORT_DATA_OFFSET: usize = TD_RTMR_OFFSET + 4 * RTMR_LEN;
+/// Offset of the TDX 1.5 `tee_tcb_svn_2`, just past the end of a TDX 1.0 body.
+pub const TD_TEE_TCB_SVN_2_OFFSET: usize = TD_REPORT_DATA_OFFSET + REPORT_DATA_LEN;
+pub const TD_MRSERVICETD_OFFSET: usize = TD_TEE_TCB_SVN_2_OFFSET + TEE_TCB_SVN_LEN;
+
+/// The TDX 1.0 report body, body type 2.
+pub const TD_BODY_10_LEN: usize = TD_TEE_TCB_SVN_2_OFFSET;
+/// The TDX 1.5 report body, body type 3.
+pub const TD_BODY_15_LEN: usize = TD_MRSERVICETD_OFFSET + MRSERVICETD_LEN;
+
+pub const CPU_SVN_LEN: usize = 16;
+pub const SGX_ATTRIBUTES_LEN: usize = 16;
+pub const MR_ENCLAVE_LEN: usize = 32;
+pub const MR_SIGNER_LEN: usize = 32;
+/// The SGX enclave report, body type 1 and the layout of the QE report.
+pub const SGX_REPORT_LEN: usize = 384;
+
+/// The ECDSA-P256 signature of the QE report.
+pub const QE_REPORT_SIGNATURE_LEN: usize = 64;
+
+const _: () = assert!(TD_BODY_10_LEN == 584 && TD_BODY_15_LEN == 648);
@@ -0,0 +1,1269 @@
+//! Parser for Intel TDX DCAP quotes.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+/// Forwards to `log::trace!` with the `logging` feature and expands to nothing otherwise.
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "logging")]
+        log::trace!($($arg)*)
+    };
+}
+
+/// Implements `Debug` like the derive would, but renders the fields marked
+/// `hex` (byte arrays and vectors), `opt_hex` (optional byte arrays),
+/// `hex_list` (lists of byte arrays) and `opt_hex_list` (optional lists) as
+/// lowercase hex instead of lists of decimal bytes.
+macro_rules! hex_debug {
+    ($ty:ident $(<$lt:lifetime>)? { $($field:ident $(: $kind:ident)?),* $(,)? }) => {
+        impl $(<$lt>)? core::fmt::Debug for $ty $(<$lt>)? {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($ty))
+                    $(.field(stringify!($field), &hex_debug!(@field self.$field $(, $kind)?)))*
+                    .finish()
+            }
+        }
+    };
+    (@field $value:expr) => { $value };
+    (@field $value:expr, hex) => { crate::display::Hex($value.as_ref(), crate::HexFormat::Plain) };
+    (@field $value:expr, opt_hex) => { $value.as_ref().map(|bytes| crate::display::Hex(bytes.as_ref(), crate::HexFormat::Plain)) };
+    (@field $value:expr, hex_list) => { crate::display::HexList(&$value) };
+    (@field $value:expr, opt_hex_list) => { $value.as_ref().map(|list| crate::display::HexList(list)) };
+}
+
+/// Forwards to `log::debug!` with the `logging` feature and expands to nothing otherwise.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "logging")]
+        log::debug!($($arg)*)
+    };
+}
+
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, string::ToString, vec::Vec};
+#[cfg(feature = "alloc")]
+use base64::Engine;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+mod attributes;
+#[cfg(feature = "azure")]
+pub mod azure;
+mod borrowed;
+#[cfg(feature = "alloc")]
+mod certification;
+#[cfg(feature = "collateral")]
+pub mod collateral;
+#[cfg(feature = "alloc")]
+mod diff;
+mod display;
+#[cfg(feature = "alloc")]
+mod encode;
+#[cfg(feature = "eventlog")]
+pub mod eventlog;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fingerprint")]
+mod fingerprint;
+#[cfg(feature = "gzip")]
+mod gzip;
+pub mod layout;
+#[cfg(feature = "linux")]
+pub mod linux;
+mod measurement;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "verify")]
+mod pck;
+#[cfg(feature = "alloc")]
+mod policy;
+mod reader;
+#[cfg(feature = "serde")]
+mod serde_hex;
+#[cfg(feature = "alloc")]
+mod sgx_v3;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "alloc")]
+mod summary;
+#[cfg(feature = "testvec")]
+pub mod testvec;
+#[cfg(feature = "verify")]
+mod verify;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+use layout::*;
+use reader::Reader;
+#[cfg(feature = "alloc")]
+pub use reader::FieldSpan;
+
+pub use attributes::{MiscSelect, SeamAttributes, SgxAttributes, TdAttributes, TeeTcbSvn, Xfam};
+pub use borrowed::{QuoteBodyKindRef, QuoteRef, TDQuoteBodyRef};
+#[cfg(feature = "alloc")]
+pub use certification::{CertificationData, CertificationDataKind, PckId, PCK_CERT_CHAIN_CERT_DATA_TYPE};
+#[cfg(feature = "collateral")]
+pub use collateral::{verify_quote, verify_quote_with_root_ca, verify_stream, verify_stream_with_root_ca, Collateral, VerifiedQuote};
+#[cfg(feature = "alloc")]
+pub use diff::FieldDiff;
+pub use display::HexFormat;
+#[cfg(feature = "gzip")]
+pub use gzip::{gunzip_if_compressed, parse_maybe_gzip, MAX_QUOTE_LEN};
+pub use measurement::{Measurement, Measurement32, Measurement48, Measurement64};
+#[cfg(feature = "verify")]
+pub use pck::PckExtensions;
+#[cfg(feature = "alloc")]
+pub use policy::{Policy, PolicyCheck, PolicyResult, TD_ATTRIBUTES_DEBUG};
+#[cfg(feature = "alloc")]
+pub use sgx_v3::{is_sgx_quote_v3, parse_sgx_quote_v3, SgxQuoteV3, SgxQuoteV3Header};
+#[cfg(feature = "alloc")]
+pub use summary::QuoteSummary;
+#[cfg(feature = "verify")]
+pub use verify::{VerifyError, INTEL_SGX_ROOT_CA};
+#[cfg(feature = "wasm")]
+pub use wasm::parse_quote_json;
+
+/// Errors returned while decoding a quote.
+///
+/// With `std`, errors from the underlying I/O, hex and base64 decoders are
+/// reported through [`std::error::Error::source`].
+#[derive(Debug, thiserror::Error)]
+pub enum ParseError {
+    #[error("unexpected EOF at offset {offset} while reading {field}")]
+    UnexpectedEof { offset: usize, field: &'static str },
+    #[error("invalid TEE type: {0:#010x}")]
+    InvalidTeeType(u32),
+    #[error("unsupported quote version: {0}")]
+    UnsupportedVersion(u16),
+    #[error("quote body size mismatch: declared {declared} bytes, expected {expected}")]
+    BodySizeMismatch { declared: u32, expected: usize },
+    #[error("unsupported certification data type: {0}")]
+    UnsupportedCertificationDataType(u16),
+    #[error("invalid PEM certificate chain")]
+    InvalidCertChain,
+    #[error("unknown QE vendor ID: {0}")]
+    UnknownQeVendor(Uuid),
+    #[error("malformed CCEL event log")]
+    InvalidEventLog,
+    #[error("unsupported quote body type: {0}")]
+    UnsupportedBodyType(u16),
+    #[cfg(feature = "alloc")]
+    #[error("invalid base64 quote")]
+    Base64(#[cfg_attr(feature = "std", from)] base64::DecodeError),
+    #[error("invalid hex")]
+    Hex(#[cfg_attr(feature = "std", from)] hex::FromHexError),
+    #[error("{} trailing bytes after the {consumed}-byte quote", len - consumed)]
+    TrailingData { consumed: usize, len: usize },
+    #[error("missing or malformed SGX extension in the PCK certificate")]
+    InvalidPckExtension,
+    #[error("measurement is {len} bytes, expected {expected}")]
+    InvalidMeasurementLength { expected: usize, len: usize },
+    #[error("reserved field {field} is not zero")]
+    NonZeroReserved { field: &'static str },
+    #[error("unsupported attestation key type: {0}")]
+    UnsupportedAttestationKeyType(AttestationKeyType),
+    #[cfg(feature = "json")]
+    #[error("JSON envelope has no string quote field")]
+    InvalidJsonEnvelope,
+    #[cfg(feature = "collateral")]
+    #[error("malformed PCS collateral")]
+    InvalidCollateral(#[source] serde_json::Error),
+    #[cfg(feature = "gzip")]
+    #[error("decompressed quote exceeds {limit} bytes")]
+    DecompressedTooLarge { limit: usize },
+    #[cfg(feature = "std")]
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+}
+
+// Without `std` the decoder errors do not implement `Error`, which `#[from]`
+// requires for the source, so the conversions are written out.
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+impl From<base64::DecodeError> for ParseError {
+    fn from(err: base64::DecodeError) -> Self {
+        ParseError::Base64(err)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl From<hex::FromHexError> for ParseError {
+    fn from(err: hex::FromHexError) -> Self {
+        ParseError::Hex(err)
+    }
+}
+
+/// Trusted execution environment that produced the quote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
+pub enum TEEType {
+    /// `0x00000000`
+    SGX,
+    /// `0x00000081`
+    TDX,
+    /// A value Intel has not assigned yet, kept as read.
+    Unknown(u32),
+}
+
+impl From<u32> for TEEType {
+    fn from(value: u32) -> Self {
+        match value {
+            0x00000000 => TEEType::SGX,
+            0x00000081 => TEEType::TDX,
+            other => TEEType::Unknown(other),
+        }
+    }
+}
+
+impl From<TEEType> for u32 {
+    fn from(tee_type: TEEType) -> Self {
+        match tee_type {
+            TEEType::SGX => 0x00000000,
+            TEEType::TDX => 0x00000081,
+            TEEType::Unknown(other) => other,
+        }
+    }
+}
+
+/// Algorithm of the attestation key that signs the quote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AttestationKeyType {
+    /// `2`, ECDSA-256-with-P-256.
+    EcdsaP256,
+    /// `3`, ECDSA-384-with-P-384.
+    EcdsaP384,
+    /// A value Intel has not assigned, kept as read.
+    Unknown(u16),
+}
+
+impl AttestationKeyType {
+    /// Length of the raw `r || s` quote signature, or `None` if unknown.
+    pub fn signature_len(self) -> Option<usize> {
+        match self {
+            AttestationKeyType::EcdsaP256 => Some(64),
+            AttestationKeyType::EcdsaP384 => Some(96),
+            AttestationKeyType::Unknown(_) => None,
+        }
+    }
+
+    /// Length of the raw `x || y` attestation public key, or `None` if unknown.
+    pub fn attestation_key_len(self) -> Option<usize> {
+        self.signature_len()
+    }
+}
+
+impl From<u16> for AttestationKeyType {
+    fn from(value: u16) -> Self {
+        match value {
+            2 => AttestationKeyType::EcdsaP256,
+            3 => AttestationKeyType::EcdsaP384,
+            other => AttestationKeyType::Unknown(other),
+        }
+    }
+}
+
+impl From<AttestationKeyType> for u16 {
+    fn from(key_type: AttestationKeyType) -> Self {
+        match key_type {
+            AttestationKeyType::EcdsaP256 => 2,
+            AttestationKeyType::EcdsaP384 => 3,
+            AttestationKeyType::Unknown(other) => other,
+        }
+    }
+}
+
+/// QE vendor ID of the Intel Quoting Enclave, `939a7233-f79c-4ca9-940a-0db3957f0607`.
+pub const INTEL_QE_VENDOR_ID: Uuid = Uuid::from_bytes([
+    0x93, 0x9a, 0x72, 0x33, 0xf7, 0x9c, 0x4c, 0xa9, 0x94, 0x0a, 0x0d, 0xb3, 0x95, 0x7f, 0x06, 0x07,
+]);
+
+/// `mrsignerseam` of a TDX module signed by Intel, which is all zeros.
+pub const INTEL_MRSIGNERSEAM: [u8; MRSIGNERSEAM_LEN] = [0; MRSIGNERSEAM_LEN];
+
+/// The 48-byte quote header.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuoteHeader {
+    pub version: u16,
+    pub attestation_key_type: AttestationKeyType,
+    pub tee_type: TEEType,
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub reserved1: [u8; 2],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub reserved2: [u8; 2],
+    pub qe_vendor_id: Uuid,
+    /// QE-defined data. Intel's quote generation library puts a 16-byte
+    /// platform identifier, linking the PCK certificate to the encrypted PPID,
+    /// in the first bytes and zeros the rest. Other QEs may leave it all zero.
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub user_data: [u8; USER_DATA_LEN],
+}
+
+/// A v4 TDX header with an ECDSA-256 attestation key type, a nil QE vendor ID
+/// and zeroed reserved and user data bytes.
+impl Default for QuoteHeader {
+    fn default() -> Self {
+        QuoteHeader {
+            version: 4,
+            attestation_key_type: AttestationKeyType::EcdsaP256,
+            tee_type: TEEType::TDX,
+            reserved1: [0; 2],
+            reserved2: [0; 2],
+            qe_vendor_id: Uuid::nil(),
+            user_data: [0; USER_DATA_LEN],
+        }
+    }
+}
+
+/// The TD report body carrying the TD measurements.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+pub struct TDQuoteBody {
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub tee_tcb_svn: [u8; TEE_TCB_SVN_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub mrseam: [u8; MRSEAM_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub mrsignerseam: [u8; MRSIGNERSEAM_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub seamattributes: [u8; SEAM_ATTRIBUTES_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub tdattributes: [u8; TD_ATTRIBUTES_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub xfam: [u8; XFAM_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub mrtd: [u8; MRTD_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub mrconfigid: [u8; MRCONFIGID_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub mrowner: [u8; MROWNER_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub mrownerconfig: [u8; MROWNERCONFIG_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub rtmr0: [u8; RTMR_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub rtmr1: [u8; RTMR_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub rtmr2: [u8; RTMR_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub rtmr3: [u8; RTMR_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub reportdata: [u8; REPORT_DATA_LEN],
+    /// Only present in the TDX 1.5 body (type 3). `None` for the 584-byte TDX
+    /// 1.0 body, whether it comes from a v4 or a v5 quote.
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex::option"))]
+    pub tee_tcb_svn_2: Option<[u8; TEE_TCB_SVN_LEN]>,
+    /// Only present in the TDX 1.5 body (type 3), like `tee_tcb_svn_2`.
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex::option"))]
+    pub mrservicetd: Option<[u8; MRSERVICETD_LEN]>,
+}
+
+/// The report body of a quote, laid out according to its [`QuoteBodyType`].
+///
+/// The body type rather than the TEE type selects the layout, so a TDX quote
+/// carrying a type 1 body decodes as an SGX enclave report.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(clippy::large_enum_variant)]
+pub enum QuoteBodyKind {
+    /// Body type 1.
+    Sgx(SgxQuoteBody),
+    /// Body types 2 and 3.
+    Tdx(TDQuoteBody),
+}
+
+impl QuoteBodyKind {
+    /// Returns the TD report body, if this is a TDX quote.
+    pub fn as_tdx(&self) -> Option<&TDQuoteBody> {
+        match self {
+            QuoteBodyKind::Tdx(body) => Some(body),
+            QuoteBodyKind::Sgx(_) => None,
+        }
+    }
+
+    /// Returns the SGX enclave report body, if this is an SGX quote.
+    pub fn as_sgx(&self) -> Option<&SgxQuoteBody> {
+        match self {
+            QuoteBodyKind::Sgx(body) => Some(body),
+            QuoteBodyKind::Tdx(_) => None,
+        }
+    }
+}
+
+impl QuoteHeader {
+    /// Returns true if the quote was produced by the Intel Quoting Enclave.
+    pub fn is_intel_qe(&self) -> bool {
+        self.qe_vendor_id == INTEL_QE_VENDOR_ID
+    }
+
+    /// Returns true if `user_data` carries no QE-defined data at all.
+    pub fn user_data_is_zero(&self) -> bool {
+        self.user_data == [0; USER_DATA_LEN]
+    }
+
+    /// The first 16 bytes of `user_data`, where Intel's QE places the
+    /// platform identifier.
+    pub fn platform_id(&self) -> [u8; PLATFORM_ID_LEN] {
+        let mut platform_id = [0u8; PLATFORM_ID_LEN];
+        platform_id.copy_from_slice(&self.user_data[..PLATFORM_ID_LEN]);
+        platform_id
+    }
+}
+
+/// A TDX 1.0 body with every field zeroed.
+impl Default for TDQuoteBody {
+    fn default() -> Self {
+        TDQuoteBody {
+            tee_tcb_svn: [0; TEE_TCB_SVN_LEN],
+            mrseam: [0; MRSEAM_LEN],
+            mrsignerseam: [0; MRSIGNERSEAM_LEN],
+            seamattributes: [0; SEAM_ATTRIBUTES_LEN],
+            tdattributes: [0; TD_ATTRIBUTES_LEN],
+            xfam: [0; XFAM_LEN],
+            mrtd: [0; MRTD_LEN],
+            mrconfigid: [0; MRCONFIGID_LEN],
+            mrowner: [0; MROWNER_LEN],
+            mrownerconfig: [0; MROWNERCONFIG_LEN],
+            rtmr0: [0; RTMR_LEN],
+            rtmr1: [0; RTMR_LEN],
+            rtmr2: [0; RTMR_LEN],
+            rtmr3: [0; RTMR_LEN],
+            reportdata: [0; REPORT_DATA_LEN],
+            tee_tcb_svn_2: None,
+            mrservicetd: None,
+        }
+    }
+}
+
+impl TDQuoteBody {
+    /// Decodes the `tdattributes` bitfield.
+    pub fn tdattributes_decoded(&self) -> TdAttributes {
+        TdAttributes::from_bytes(self.tdattributes)
+    }
+
+    /// Decodes the `seamattributes` bitfield.
+    pub fn seamattributes_decoded(&self) -> SeamAttributes {
+        SeamAttributes::from_bytes(self.seamattributes)
+    }
+
+    /// Whether the TD runs in debug mode (TUD bit 0 of `tdattributes`), which
+    /// lets the host inspect its memory and state.
+    pub fn is_debug(&self) -> bool {
+        self.tdattributes[0] & 1 != 0
+    }
+
+    /// Whether the TDX module was signed by Intel, i.e. `mrsignerseam` is
+    /// [`INTEL_MRSIGNERSEAM`].
+    pub fn is_intel_signed_seam(&self) -> bool {
+        self.mrsignerseam == INTEL_MRSIGNERSEAM
+    }
+
+    /// Decodes the `xfam` bitmask of enabled XSTATE components.
+    pub fn xfam_decoded(&self) -> Xfam {
+        Xfam::from_bytes(self.xfam)
+    }
+
+    /// Splits `tee_tcb_svn` into its named SVN components.
+    pub fn tee_tcb_svn_components(&self) -> TeeTcbSvn {
+        TeeTcbSvn::from_bytes(self.tee_tcb_svn)
+    }
+
+    /// Yields `tee_tcb_svn` as `("tdxtcbcomp01", svn)` through `("tdxtcbcomp16", svn)`,
+    /// matching the `tdxtcbcomponents` of Intel TCB info.
+    pub fn tee_tcb_svn_labeled(&self) -> impl Iterator<Item = (&'static str, u8)> {
+        attributes::labeled_tcb_components(self.tee_tcb_svn)
+    }
+
+    /// Same as [`tee_tcb_svn_labeled`](Self::tee_tcb_svn_labeled) for
+    /// `tee_tcb_svn_2`, which only TDX 1.5 bodies carry.
+    pub fn tee_tcb_svn_2_labeled(&self) -> Option<impl Iterator<Item = (&'static str, u8)>> {
+        self.tee_tcb_svn_2.map(attributes::labeled_tcb_components)
+    }
+
+    /// Splits `reportdata` into its first and second 32 bytes, which protocols
+    /// commonly use for a public key hash and a nonce.
+    pub fn report_data_halves(&self) -> ([u8; REPORT_DATA_LEN / 2], [u8; REPORT_DATA_LEN / 2]) {
+        let mut first = [0u8; REPORT_DATA_LEN / 2];
+        let mut second = [0u8; REPORT_DATA_LEN / 2];
+        first.copy_from_slice(&self.reportdata[..REPORT_DATA_LEN / 2]);
+        second.copy_from_slice(&self.reportdata[REPORT_DATA_LEN / 2..]);
+        (first, second)
+    }
+
+    /// Compares `mrtd` with `expected` in constant time.
+    pub fn ct_eq_mrtd(&self, expected: &[u8; MRTD_LEN]) -> bool {
+        self.mrtd.ct_eq(expected).into()
+    }
+
+    /// Compares `reportdata` with `expected` in constant time.
+    pub fn ct_eq_reportdata(&self, expected: &[u8; REPORT_DATA_LEN]) -> bool {
+        self.reportdata.ct_eq(expected).into()
+    }
+
+    /// The four runtime measurement registers, `rtmr0` first.
+    pub fn rtmrs(&self) -> [[u8; RTMR_LEN]; 4] {
+        [self.rtmr0, self.rtmr1, self.rtmr2, self.rtmr3]
+    }
+
+    /// RTMR `index` (0-3), or `None` for any other index.
+    pub fn rtmr(&self, index: usize) -> Option<&[u8; RTMR_LEN]> {
+        match index {
+            0 => Some(&self.rtmr0),
+            1 => Some(&self.rtmr1),
+            2 => Some(&self.rtmr2),
+            3 => Some(&self.rtmr3),
+            _ => None,
+        }
+    }
+
+    /// Compares RTMR `index` (0-3) with `expected` in constant time. Returns
+    /// `false` for any other index.
+    pub fn ct_eq_rtmr(&self, index: usize, expected: &[u8; RTMR_LEN]) -> bool {
+        self.rtmr(index).is_some_and(|rtmr| rtmr.ct_eq(expected).into())
+    }
+
+    /// [`report_data_halves`](Self::report_data_halves), hex encoded.
+    #[cfg(feature = "alloc")]
+    pub fn report_data_hex_halves(&self) -> (String, String) {
+        let (first, second) = self.report_data_halves();
+        (hex::encode(first), hex::encode(second))
+    }
+}
+
+/// Layout of the report body, as named by the body descriptor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum QuoteBodyType {
+    /// Type 1: the 384-byte SGX enclave report.
+    SgxReport,
+    /// Type 2: the 584-byte TDX 1.0 report body.
+    Tdx10,
+    /// Type 3: the 648-byte TDX 1.5 report body, which adds `tee_tcb_svn_2` and `mrservicetd`.
+    Tdx15,
+    Unknown(u16),
+}
+
+impl QuoteBodyType {
+    /// Length of the body layout, or `None` if the type is unknown.
+    pub fn body_size(&self) -> Option<usize> {
+        match self {
+            QuoteBodyType::SgxReport => Some(SGX_REPORT_LEN),
+            QuoteBodyType::Tdx10 => Some(TD_BODY_10_LEN),
+            QuoteBodyType::Tdx15 => Some(TD_BODY_15_LEN),
+            QuoteBodyType::Unknown(_) => None,
+        }
+    }
+}
+
+impl From<u16> for QuoteBodyType {
+    fn from(value: u16) -> Self {
+        match value {
+            1 => QuoteBodyType::SgxReport,
+            2 => QuoteBodyType::Tdx10,
+            3 => QuoteBodyType::Tdx15,
+            other => QuoteBodyType::Unknown(other),
+        }
+    }
+}
+
+impl From<QuoteBodyType> for u16 {
+    fn from(body_type: QuoteBodyType) -> Self {
+        match body_type {
+            QuoteBodyType::SgxReport => 1,
+            QuoteBodyType::Tdx10 => 2,
+            QuoteBodyType::Tdx15 => 3,
+            QuoteBodyType::Unknown(other) => other,
+        }
+    }
+}
+
+/// The report body together with its type and size.
+///
+/// v5 quotes encode the type and size in front of the body. v4 quotes have no
+/// such descriptor, so both values are derived from the TEE type instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuoteBody {
+    pub td_quote_body_type: QuoteBodyType,
+    pub size: u32,
+    pub td_quote_body: QuoteBodyKind,
+}
+
+/// A zeroed TDX 1.0 body with its matching type and size.
+impl Default for QuoteBody {
+    fn default() -> Self {
+        let td_quote_body_type = QuoteBodyType::Tdx10;
+        QuoteBody {
+            td_quote_body_type,
+            size: td_quote_body_type.body_size().unwrap_or_default() as u32,
+            td_quote_body: QuoteBodyKind::Tdx(TDQuoteBody::default()),
+        }
+    }
+}
+
+/// The 384-byte SGX enclave report body.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+pub struct SgxQuoteBody {
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub cpu_svn: [u8; CPU_SVN_LEN],
+    pub misc_select: u32,
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub reserved1: [u8; 28],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub attributes: [u8; SGX_ATTRIBUTES_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub mr_enclave: [u8; MR_ENCLAVE_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub reserved2: [u8; 32],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub mr_signer: [u8; MR_SIGNER_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub reserved3: [u8; 96],
+    pub isv_prod_id: u16,
+    pub isv_svn: u16,
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub reserved4: [u8; 60],
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub report_data: [u8; REPORT_DATA_LEN],
+}
+
+/// An SGX report body with every field zeroed.
+impl Default for SgxQuoteBody {
+    fn default() -> Self {
+        SgxQuoteBody {
+            cpu_svn: [0; CPU_SVN_LEN],
+            misc_select: 0,
+            reserved1: [0; 28],
+            attributes: [0; SGX_ATTRIBUTES_LEN],
+            mr_enclave: [0; MR_ENCLAVE_LEN],
+            reserved2: [0; 32],
+            mr_signer: [0; MR_SIGNER_LEN],
+            reserved3: [0; 96],
+            isv_prod_id: 0,
+            isv_svn: 0,
+            reserved4: [0; 60],
+            report_data: [0; REPORT_DATA_LEN],
+        }
+    }
+}
+
+impl SgxQuoteBody {
+    /// Decodes the `misc_select` bitfield.
+    pub fn misc_select_decoded(&self) -> MiscSelect {
+        MiscSelect::from_u32(self.misc_select)
+    }
+
+    /// Decodes the `attributes` flags and XFRM.
+    pub fn attributes_decoded(&self) -> SgxAttributes {
+        SgxAttributes::from_bytes(self.attributes)
+    }
+}
+
+/// The Quoting Enclave report, which uses the SGX enclave report layout.
+pub type QeReport = SgxQuoteBody;
+
+/// The ECDSA quote signature section that follows the report body.
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuoteSignatureData {
+    pub signature_data_len: u32,
+    /// Raw `r || s` quote signature, 64 bytes for P-256 and 96 for P-384.
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex::vec"))]
+    pub signature: Vec<u8>,
+    /// Raw `x || y` attestation public key, the same length as `signature`.
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex::vec"))]
+    pub attestation_key: Vec<u8>,
+    pub qe_cert_data_type: u16,
+    pub qe_cert_data_size: u32,
+    pub qe_report: QeReport,
+    /// ECDSA-P256 signature of the QE report by the PCK key, whatever the
+    /// attestation key type.
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex"))]
+    pub qe_report_signature: [u8; QE_REPORT_SIGNATURE_LEN],
+    /// Length-prefixed QE authentication data, hashed into the QE report data.
+    #[cfg_attr(feature = "serde", serde(with = "serde_hex::vec"))]
+    pub qe_auth_data: Vec<u8>,
+    pub certification_data: CertificationData,
+}
+
+/// Tag of an uncompressed SEC1 elliptic curve point.
+#[cfg(feature = "alloc")]
+const SEC1_UNCOMPRESSED_TAG: u8 = 0x04;
+
+#[cfg(feature = "alloc")]
+impl QuoteSignatureData {
+    /// The attestation key as an uncompressed SEC1 point, `0x04 || x || y`, as
+    /// most crypto libraries expect it: 65 bytes for P-256 and 97 for P-384.
+    pub fn attestation_pubkey_sec1(&self) -> Vec<u8> {
+        let mut sec1 = Vec::with_capacity(1 + self.attestation_key.len());
+        sec1.push(SEC1_UNCOMPRESSED_TAG);
+        sec1.extend_from_slice(&self.attestation_key);
+        sec1
+    }
+}
+
+/// A parsed quote.
+#[cfg(feature = "alloc")]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(from = "QuoteFields"))]
+pub struct Quote {
+    pub header: QuoteHeader,
+    pub body: QuoteBody,
+    pub signature: QuoteSignatureData,
+    /// The header and body bytes exactly as they were parsed; this is the
+    /// message covered by the quote signature. It is not serialized, and a
+    /// deserialized quote re-encodes it from `header` and `body`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    signed_data: Vec<u8>,
+}
+
+/// `signed_data` carries a copy of the report data, so it is wiped as well.
+#[cfg(all(feature = "alloc", feature = "zeroize"))]
+impl Drop for Quote {
+    fn drop(&mut self) {
+        self.signed_data.zeroize();
+    }
+}
+
+/// The serialized fields of a [`Quote`].
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct QuoteFields {
+    header: QuoteHeader,
+    body: QuoteBody,
+    signature: QuoteSignatureData,
+}
+
+#[cfg(feature = "serde")]
+impl From<QuoteFields> for Quote {
+    fn from(fields: QuoteFields) -> Quote {
+        let signed_data = encode::encode_signed_data(&fields.header, &fields.body);
+        Quote { header: fields.header, body: fields.body, signature: fields.signature, signed_data }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Quote {
+    /// The certification data nested inside the QE report certification data.
+    pub fn certification_data(&self) -> &CertificationData {
+        &self.signature.certification_data
+    }
+
+    /// The header and body bytes, exactly as read, that the quote signature covers.
+    pub fn signed_data(&self) -> &[u8] {
+        &self.signed_data
+    }
+
+    /// Total length of the encoded quote: the header, the v5 body descriptor,
+    /// the body and the signature section with its length prefix, as declared
+    /// by the quote's `size` and `signature_data_len` fields.
+    pub fn encoded_len(&self) -> usize {
+        let descriptor_len = if self.header.version == 5 { BODY_DESCRIPTOR_LEN } else { 0 };
+        HEADER_LEN + descriptor_len + self.body.size as usize + SIGNATURE_DATA_LEN_LEN + self.signature.signature_data_len as usize
+    }
+
+    /// Decodes a standard base64 quote, as returned by most attestation APIs,
+    /// and parses it. ASCII whitespace, including line wrapping, is ignored.
+    pub fn from_base64(s: &str) -> Result<Quote, ParseError> {
+        let encoded: String = s.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+        let data = base64::engine::general_purpose::STANDARD.decode(encoded)?;
+        parse_quote(&data)
+    }
+
+    /// Parses a quote carried as a hex or base64 string in the top-level
+    /// `field` of a JSON object, such as `{"quote": "..."}`.
+    ///
+    /// A string made only of an even number of hex digits is decoded as hex,
+    /// anything else as base64.
+    #[cfg(feature = "json")]
+    pub fn from_json_envelope(json: &str, field: &str) -> Result<Quote, ParseError> {
+        let envelope: serde_json::Value = serde_json::from_str(json).map_err(|_| ParseError::InvalidJsonEnvelope)?;
+        let encoded = envelope.get(field).and_then(|value| value.as_str()).ok_or(ParseError::InvalidJsonEnvelope)?;
+        let encoded = encoded.trim();
+        if encoded.len() % 2 == 0 && encoded.bytes().all(|b| b.is_ascii_hexdigit()) {
+            let data = hex::decode(encoded)?;
+            return parse_quote(&data);
+        }
+        Quote::from_base64(encoded)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&[u8]> for Quote {
+    type Error = ParseError;
+
+    /// Same as [`parse_quote`].
+    fn try_from(data: &[u8]) -> Result<Quote, ParseError> {
+        parse_quote(data)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<Vec<u8>> for Quote {
+    type Error = ParseError;
+
+    /// Same as [`parse_quote`].
+    fn try_from(data: Vec<u8>) -> Result<Quote, ParseError> {
+        parse_quote(&data)
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Certification data type wrapping the QE report, its signature and the
+/// nested certification data.
+const QE_REPORT_CERT_DATA_TYPE: u16 = 6;
+
+fn parse_sgx_quote_body(reader: &mut Reader<'_>) -> Result<SgxQuoteBody, ParseError> {
+    Ok(SgxQuoteBody {
+        cpu_svn: reader.read_bytes("cpu_svn")?,
+        misc_select: reader.read_u32("misc_select")?,
+        reserved1: reader.read_bytes("reserved1")?,
+        attributes: reader.read_bytes("attributes")?,
+        mr_enclave: reader.read_bytes("mr_enclave")?,
+        reserved2: reader.read_bytes("reserved2")?,
+        mr_signer: reader.read_bytes("mr_signer")?,
+        reserved3: reader.read_bytes("reserved3")?,
+        isv_prod_id: reader.read_u16("isv_prod_id")?,
+        isv_svn: reader.read_u16("isv_svn")?,
+        reserved4: reader.read_bytes("reserved4")?,
+        report_data: reader.read_bytes("report_data")?,
+    })
+}
+
+/// Steps over the signature section, keeping only its length.
+#[cfg(feature = "alloc")]
+fn skip_signature_data(reader: &mut Reader<'_>) -> Result<QuoteSignatureData, ParseError> {
+    let signature_data_len = reader.read_u32("signature_data_len")?;
+    reader.read_slice(signature_data_len as usize, "signature_data")?;
+    Ok(QuoteSignatureData {
+        signature_data_len,
+        signature: Vec::new(),
+        attestation_key: Vec::new(),
+        qe_cert_data_type: 0,
+        qe_cert_data_size: 0,
+        qe_report: SgxQuoteBody::default(),
+        qe_report_signature: [0; QE_REPORT_SIGNATURE_LEN],
+        qe_auth_data: Vec::new(),
+        certification_data: CertificationData { cert_data_type: 0, size: 0, data: Vec::new() },
+    })
+}
+
+#[cfg(feature = "alloc")]
+fn parse_signature_data(reader: &mut Reader<'_>, key_type: AttestationKeyType) -> Result<QuoteSignatureData, ParseError> {
+    let key_len = attestation_key_len(key_type)?;
+    let signature_data_len = reader.read_u32("signature_data_len")?;
+    let reader = &mut reader.sub_reader(signature_data_len as usize, "signature_data")?;
+    let signature = reader.read_slice(key_len, "signature")?.to_vec();
+    let attestation_key = reader.read_slice(key_len, "attestation_key")?.to_vec();
+
+    let qe_cert_data_type = reader.read_u16("qe_cert_data_type")?;
+    if qe_cert_data_type != QE_REPORT_CERT_DATA_TYPE {
+        return Err(ParseError::UnsupportedCertificationDataType(qe_cert_data_type));
+    }
+    let qe_cert_data_size = reader.read_u32("qe_cert_data_size")?;
+    trace!("signature data: {} bytes, QE certification data: {} bytes", signature_data_len, qe_cert_data_size);
+    // Everything below is nested in the QE report certification data, so no
+    // length inside it may run past the declared size.
+    let mut section = reader.sub_reader(qe_cert_data_size as usize, "qe_cert_data")?;
+    let qe_report = parse_sgx_quote_body(&mut section)?;
+    let qe_report_signature = section.read_bytes("qe_report_signature")?;
+
+    let qe_auth_data_len = section.read_u16("qe_auth_data_len")?;
+    let qe_auth_data = section.read_slice(qe_auth_data_len as usize, "qe_auth_data")?.to_vec();
+    trace!("QE report and {} bytes of QE authentication data parsed", qe_auth_data_len);
+
+    Ok(QuoteSignatureData {
+        signature_data_len,
+        signature,
+        attestation_key,
+        qe_cert_data_type,
+        qe_cert_data_size,
+        qe_report,
+        qe_report_signature,
+        qe_auth_data,
+        certification_data: certification::parse_certification_data(&mut section)?,
+    })
+}
+
+/// Rejects any version but 4 and 5 before more of the quote is read, so a
+/// big-endian or otherwise foreign blob fails fast instead of being decoded.
+fn check_version(version: u16) -> Result<(), ParseError> {
+    match version {
+        4 | 5 => Ok(()),
+        _ => Err(ParseError::UnsupportedVersion(version)),
+    }
+}
+
+/// Reads the 48-byte header, checking the version before the rest of it.
+fn read_header(reader: &mut Reader<'_>) -> Result<QuoteHeader, ParseError> {
+    let version = reader.read_u16("version")?;
+    check_version(version)?;
+
+    let header = QuoteHeader {
+        version,
+        attestation_key_type: AttestationKeyType::from(reader.read_u16("attestation_key_type")?),
+        tee_type: TEEType::from(reader.read_u32("tee_type")?),
+        reserved1: reader.read_bytes("reserved1")?,
+        reserved2: reader.read_bytes("reserved2")?,
+        qe_vendor_id: Uuid::from_bytes(reader.read_bytes("qe_vendor_id")?),
+        user_data: reader.read_bytes("user_data")?,
+    };
+    trace!("header parsed at offset {}: version {}, TEE type {:?}", reader.offset(), version, header.tee_type);
+    Ok(header)
+}
+
+/// Length of both the quote signature and the attestation key for
+/// `key_type`. An unknown key type leaves the signature section layout
+/// undefined, so it is rejected.
+fn attestation_key_len(key_type: AttestationKeyType) -> Result<usize, ParseError> {
+    key_type.attestation_key_len().ok_or(ParseError::UnsupportedAttestationKeyType(key_type))
+}
+
+/// Rejects a v5 body descriptor whose size does not match its body type.
+fn check_body_size(td_quote_body_type: QuoteBodyType, size: u32) -> Result<(), ParseError> {
+    match td_quote_body_type.body_size() {
+        Some(expected) if size as usize != expected => Err(ParseError::BodySizeMismatch { declared: size, expected }),
+        _ => Ok(()),
+    }
+}
+
+/// Body type implied by the TEE type of a v4 quote, which has no body descriptor.
+///
+/// The layout of an unknown TEE type cannot be inferred, so it is rejected.
+fn v4_body_type(tee_type: TEEType) -> Result<QuoteBodyType, ParseError> {
+    match tee_type {
+        TEEType::SGX => Ok(QuoteBodyType::SgxReport),
+        TEEType::TDX => Ok(QuoteBodyType::Tdx10),
+        TEEType::Unknown(tee_type) => Err(ParseError::InvalidTeeType(tee_type)),
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn parse_td_quote_body(reader: &mut Reader<'_>, tdx15: bool) -> Result<TDQuoteBody, ParseError> {
+    let mut body = TDQuoteBody {
+        tee_tcb_svn: reader.read_bytes("tee_tcb_svn")?,
+        mrseam: reader.read_bytes("mrseam")?,
+        mrsignerseam: reader.read_bytes("mrsignerseam")?,
+        seamattributes: reader.read_bytes("seamattributes")?,
+        tdattributes: reader.read_bytes("tdattributes")?,
+        xfam: reader.read_bytes("xfam")?,
+        mrtd: reader.read_bytes("mrtd")?,
+        mrconfigid: reader.read_bytes("mrconfigid")?,
+        mrowner: reader.read_bytes("mrowner")?,
+        mrownerconfig: reader.read_bytes("mrownerconfig")?,
+        rtmr0: reader.read_bytes("rtmr0")?,
+        rtmr1: reader.read_bytes("rtmr1")?,
+        rtmr2: reader.read_bytes("rtmr2")?,
+        rtmr3: reader.read_bytes("rtmr3")?,
+        reportdata: reader.read_bytes("reportdata")?,
+        tee_tcb_svn_2: None,
+        mrservicetd: None,
+    };
+    if tdx15 {
+        body.tee_tcb_svn_2 = Some(reader.read_bytes("tee_tcb_svn_2")?);
+        body.mrservicetd = Some(reader.read_bytes("mrservicetd")?);
+    }
+    Ok(body)
+}
+
+#[cfg(feature = "alloc")]
+impl TDQuoteBody {
+    /// Parses a standalone TD report body in the quote layout, outside any
+    /// quote. A 584-byte body is read as TDX 1.0 and a 648-byte one as TDX 1.5.
+    ///
+    /// This is the body as it appears in a quote, not the 1024-byte TDREPORT
+    /// structure returned by `TDG.MR.REPORT`, which lays the same values out
+    /// differently.
+    pub fn parse(data: &[u8]) -> Result<TDQuoteBody, ParseError> {
+        let tdx15 = match data.len() {
+            TD_BODY_10_LEN => false,
+            TD_BODY_15_LEN => true,
+            // A body longer than TDX 1.0 is taken for a malformed TDX 1.5 one.
+            len => {
+                let expected = if len > TD_BODY_10_LEN { TD_BODY_15_LEN } else { TD_BODY_10_LEN };
+                return Err(ParseError::BodySizeMismatch { declared: len as u32, expected });
+            }
+        };
+        parse_td_quote_body(&mut Reader::new(data), tdx15)
+    }
+}
+
+/// Options controlling how strictly [`parse_quote_with_options`] validates a quote.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Reject quotes whose QE vendor ID is not [`INTEL_QE_VENDOR_ID`].
+    pub strict_qe_vendor: bool,
+    /// Reject TEE types other than SGX and TDX instead of keeping them as [`TEEType::Unknown`].
+    pub strict_tee_type: bool,
+    /// Reject input that continues past the end of the quote.
+    pub strict_trailing_data: bool,
+    /// Step over the signature section without decoding it. The quote's
+    /// `signature` then only carries `signature_data_len`; every other field
+    /// is zeroed or empty.
+    pub skip_signature: bool,
+    /// Reject quotes with non-zero header reserved bytes or reserved
+    /// `tdattributes` or `seamattributes` bits.
+    pub strict_reserved: bool,
+}
+
+/// Builder for parsing quotes with a chosen set of [`ParseOptions`], e.g.
+/// `QuoteParser::new().strict_qe_vendor(true).parse(&data)`.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Default)]
+pub struct QuoteParser {
+    options: ParseOptions,
+}
+
+#[cfg(feature = "alloc")]
+impl QuoteParser {
+    pub fn new() -> Self {
+        QuoteParser::default()
+    }
+
+    /// See [`ParseOptions::strict_qe_vendor`].
+    pub fn strict_qe_vendor(mut self, strict: bool) -> Self {
+        self.options.strict_qe_vendor = strict;
+        self
+    }
+
+    /// See [`ParseOptions::strict_tee_type`].
+    pub fn strict_tee_type(mut self, strict: bool) -> Self {
+        self.options.strict_tee_type = strict;
+        self
+    }
+
+    /// See [`ParseOptions::strict_reserved`].
+    pub fn strict_reserved(mut self, strict: bool) -> Self {
+        self.options.strict_reserved = strict;
+        self
+    }
+
+    /// Whether to decode the signature section; see [`ParseOptions::skip_signature`].
+    pub fn parse_signature(mut self, parse: bool) -> Self {
+        self.options.skip_signature = !parse;
+        self
+    }
+
+    /// See [`ParseOptions::strict_trailing_data`].
+    pub fn require_exact_len(mut self, exact: bool) -> Self {
+        self.options.strict_trailing_data = exact;
+        self
+    }
+
+    /// The options this builder has collected.
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    pub fn parse(&self, data: &[u8]) -> Result<Quote, ParseError> {
+        parse_quote_with_options(data, &self.options)
+    }
+}
+
+/// Parses only the 48-byte quote header, leaving the body and signature
+/// unread, for cheaply filtering quotes by version or TEE type.
+pub fn parse_header(data: &[u8]) -> Result<QuoteHeader, ParseError> {
+    read_header(&mut Reader::new(data))
+}
+
+/// Parses a version 4 or version 5 quote from its raw little-endian encoding.
+#[cfg(feature = "alloc")]
+pub fn parse_quote(data: &[u8]) -> Result<Quote, ParseError> {
+    parse_quote_with_options(data, &ParseOptions::default())
+}
+
+/// Parses a quote like [`parse_quote`], applying the checks enabled in `options`.
+#[cfg(feature = "alloc")]
+pub fn parse_quote_with_options(data: &[u8], options: &ParseOptions) -> Result<Quote, ParseError> {
+    let (quote, consumed) = parse_quote_prefix(data, options)?;
+    if options.strict_trailing_data && consumed != data.len() {
+        return Err(ParseError::TrailingData { consumed, len: data.len() });
+    }
+    Ok(quote)
+}
+
+/// Parses a quote like [`parse_quote`] and also returns the number of bytes it
+/// took up, so callers can detect trailing data.
+#[cfg(feature = "alloc")]
+pub fn parse_quote_with_len(data: &[u8]) -> Result<(Quote, usize), ParseError> {
+    parse_quote_prefix(data, &ParseOptions::default())
+}
+
+/// Parses a quote like [`parse_quote`] and also returns where each field was
+/// read from, in read order. Length-prefixed sections such as
+/// `signature_data` get a span of their own ahead of the fields inside them.
+#[cfg(feature = "alloc")]
+pub fn parse_quote_with_spans(data: &[u8]) -> Result<(Quote, Vec<FieldSpan>), ParseError> {
+    let spans = core::cell::RefCell::new(Vec::new());
+    let (quote, _) = parse_quote_from(Reader::with_spans(data, &spans), data, &ParseOptions::default())?;
+    Ok((quote, spans.into_inner()))
+}
+
+/// Splits `data` into the quote at its start and whatever follows it, such as
+/// collateral appended by an exporter. The quote is parsed to find its length.
+#[cfg(feature = "alloc")]
+pub fn split_quote_and_trailer(data: &[u8]) -> Result<(&[u8], &[u8]), ParseError> {
+    let (_, len) = parse_quote_prefix(data, &ParseOptions::default())?;
+    Ok(data.split_at(len))
+}
+
+/// Parses the quote at the start of `data` together with a TD report body
+/// appended after it, as some service TD and migration TD setups do.
+///
+/// The DCAP quote format defines no such trailer, so any trailer of exactly
+/// 584 or 648 bytes is taken to be a TDX 1.0 or 1.5 body and parsed with
+/// [`TDQuoteBody::parse`]. Trailers of any other length, like the padding
+/// some exporters add, give `None`.
+#[cfg(feature = "alloc")]
+pub fn parse_quote_with_appended_report(data: &[u8]) -> Result<(Quote, Option<TDQuoteBody>), ParseError> {
+    let (quote, len) = parse_quote_prefix(data, &ParseOptions::default())?;
+    let appended = match data.len() - len {
+        TD_BODY_10_LEN | TD_BODY_15_LEN => Some(TDQuoteBody::parse(&data[len..])?),
+        _ => None,
+    };
+    Ok((quote, appended))
+}
+
+/// Parses back-to-back quotes until `data` is exhausted.
+#[cfg(feature = "alloc")]
+pub fn parse_quotes(data: &[u8]) -> Result<Vec<Quote>, ParseError> {
+    let mut quotes = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let (quote, len) = parse_quote_prefix(&data[offset..], &ParseOptions::default()).map_err(|err| match err {
+            ParseError::UnexpectedEof { offset: eof, field } => ParseError::UnexpectedEof { offset: offset + eof, field },
+            err => err,
+        })?;
+        quotes.push(quote);
+        offset += len;
+    }
+    Ok(quotes)
+}
+
+#[cfg(feature = "alloc")]
+fn check_reserved(header: &QuoteHeader, body: &QuoteBody) -> Result<(), ParseError> {
+    if header.reserved1 != [0; 2] {
+        return Err(ParseError::NonZeroReserved { field: "reserved1" });
+    }
+    if header.reserved2 != [0; 2] {
+        return Err(ParseError::NonZeroReserved { field: "reserved2" });
+    }
+    if let QuoteBodyKind::Tdx(body) = &body.td_quote_body {
+        let tdattributes = body.tdattributes_decoded();
+        if tdattributes.tud_reserved != 0 || tdattributes.sec_reserved != 0 || tdattributes.other_reserved != 0 {
+            return Err(ParseError::NonZeroReserved { field: "tdattributes" });
+        }
+        if body.seamattributes_decoded().reserved != 0 {
+            return Err(ParseError::NonZeroReserved { field: "seamattributes" });
+        }
+    }
+    Ok(())
+}
+
+/// Parses the quote at the start of `data`, returning it with the number of
+/// bytes its header, body and signature sections took up.
+#[cfg(feature = "alloc")]
+fn parse_quote_prefix(data: &[u8], options: &ParseOptions) -> Result<(Quote, usize), ParseError> {
+    parse_quote_from(Reader::new(data), data, options)
+}
+
+/// [`parse_quote_prefix`] with a reader over `data` supplied by the caller.
+#[cfg(feature = "alloc")]
+fn parse_quote_from(mut reader: Reader<'_>, data: &[u8], options: &ParseOptions) -> Result<(Quote, usize), ParseError> {
+    let header = read_header(&mut reader)?;
+    let version = header.version;
+    if let TEEType::Unknown(tee_type) = header.tee_type {
+        if options.strict_tee_type {
+            return Err(ParseError::InvalidTeeType(tee_type));
+        }
+    }
+    if options.strict_qe_vendor && !header.is_intel_qe() {
+        return Err(ParseError::UnknownQeVendor(header.qe_vendor_id));
+    }
+
+    let (td_quote_body_type, size) = if version == 4 {
+        let td_quote_body_type = v4_body_type(header.tee_type)?;
+        (td_quote_body_type, td_quote_body_type.body_size().unwrap_or_default() as u32)
+    } else {
+        let td_quote_body_type = QuoteBodyType::from(reader.read_u16("td_quote_body_type")?);
+        let size = reader.read_u32("size")?;
+        check_body_size(td_quote_body_type, size)?;
+        (td_quote_body_type, size)
+    };
+    debug!("quote body type {}, {} bytes at offset {}", td_quote_body_type, size, reader.offset());
+    let td_quote_body = match td_quote_body_type {
+        QuoteBodyType::SgxReport => QuoteBodyKind::Sgx(parse_sgx_quote_body(&mut reader)?),
+        QuoteBodyType::Tdx10 => QuoteBodyKind::Tdx(parse_td_quote_body(&mut reader, false)?),
+        QuoteBodyType::Tdx15 => QuoteBodyKind::Tdx(parse_td_quote_body(&mut reader, true)?),
+        QuoteBodyType::Unknown(body_type) => return Err(ParseError::UnsupportedBodyType(body_type)),
+    };
+    let body = QuoteBody { td_quote_body_type, size, td_quote_body };
+    if options.strict_reserved {
+        check_reserved(&header, &body)?;
+    }
+
+    let signed_data = data[..reader.offset()].to_vec();
+    let signature = if options.skip_signature {
+        skip_signature_data(&mut reader)?
+    } else {
+        parse_signature_data(&mut reader, header.attestation_key_type)?
+    };
+
+    debug!("quote parsed: {} bytes, signature data {} bytes", reader.offset(), signature.signature_data_len);
+    Ok((Quote { header, body, signature, signed_data }, reader.offset()))
+}
+
+/// Decodes an `N`-byte measurement from hex, as stored in configuration.
+///
+/// Surrounding whitespace and a `0x` prefix are ignored. Odd-length input
+/// fails with [`ParseError::Hex`] and a well-formed string of the wrong size
+/// with [`ParseError::InvalidMeasurementLength`].
+pub fn parse_measurement<const N: usize>(s: &str) -> Result<[u8; N], ParseError> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    if !s.len().is_multiple_of(2) {
+        return Err(ParseError::Hex(hex::FromHexError::OddLength));
+    }
+    if s.len() / 2 != N {
+        return Err(ParseError::InvalidMeasurementLength { expected: N, len: s.len() / 2 });
+    }
+    let mut bytes = [0u8; N];
+    hex::decode_to_slice(s, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// [`parse_measurement`] for 32-byte values such as MRENCLAVE and MRSIGNER.
+pub fn parse_measurement_32(s: &str) -> Result<[u8; 32], ParseError> {
+    parse_measurement(s)
+}
+
+/// [`parse_measurement`] for 48-byte values such as MRTD and the RTMRs.
+pub fn parse_measurement_48(s: &str) -> Result<[u8; 48], ParseError> {
+    parse_measurement(s)
+}
+
+/// [`parse_measurement`] for 64-byte values such as report data.
+pub fn parse_measurement_64(s: &str) -> Result<[u8; 64], ParseError> {
+    parse_measurement(s)
+}
+
+/// Formats the TUD, SEC and OTHER groups of the `tdattributes` field.
+#[cfg(feature = "alloc")]
+pub fn extract_tdattributes_info(tdattributes: [u8; 8]) -> String {
+    TdAttributes::from_bytes(tdattributes).to_string()
+}
@@ -0,0 +1,46 @@
+//! Quote generation on a Linux TDX guest through configfs-tsm.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Directory under which the kernel exposes TSM report entries.
+const TSM_REPORT_DIR: &str = "/sys/kernel/config/tsm/report";
+
+static NEXT_ENTRY: AtomicUsize = AtomicUsize::new(0);
+
+/// A report entry that is removed again when dropped.
+struct ReportEntry(PathBuf);
+
+impl Drop for ReportEntry {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir(&self.0);
+    }
+}
+
+fn read_generation(entry: &Path) -> io::Result<String> {
+    Ok(fs::read_to_string(entry.join("generation"))?.trim().to_string())
+}
+
+/// Requests a fresh quote binding `report_data` from the TDX module and
+/// returns its raw bytes, ready for [`crate::parse_quote`].
+///
+/// A temporary entry is created under `/sys/kernel/config/tsm/report/` and
+/// removed afterwards. This requires root and a kernel with configfs-tsm
+/// mounted. An error of kind `Interrupted` means another writer raced on the
+/// same entry and the request can be retried.
+pub fn fetch_quote(report_data: [u8; 64]) -> io::Result<Vec<u8>> {
+    let name = format!("tdx-quote-parser-{}-{}", std::process::id(), NEXT_ENTRY.fetch_add(1, Ordering::Relaxed));
+    let path = Path::new(TSM_REPORT_DIR).join(name);
+    fs::create_dir(&path)?;
+    let entry = ReportEntry(path);
+
+    fs::write(entry.0.join("inblob"), report_data)?;
+    let generation = read_generation(&entry.0)?;
+    let quote = fs::read(entry.0.join("outblob"))?;
+    if read_generation(&entry.0)? != generation {
+        return Err(io::Error::new(io::ErrorKind::Interrupted, "TSM report entry was rewritten during the request"));
+    }
+    Ok(quote)
+}
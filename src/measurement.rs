@@ -0,0 +1,96 @@
+//! Typed measurement values, so measurements of different sizes cannot be
+//! compared with each other by mistake.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::display::Hex;
+use crate::layout::{
+    MRCONFIGID_LEN, MROWNERCONFIG_LEN, MROWNER_LEN, MRSEAM_LEN, MRSIGNERSEAM_LEN, MRTD_LEN, MR_ENCLAVE_LEN, MR_SIGNER_LEN,
+    REPORT_DATA_LEN, RTMR_LEN,
+};
+use crate::{parse_measurement, HexFormat, ParseError, SgxQuoteBody, TDQuoteBody};
+
+/// An `N`-byte measurement or report data value.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Measurement<const N: usize>(pub [u8; N]);
+
+/// A 32-byte measurement such as MRENCLAVE or MRSIGNER.
+pub type Measurement32 = Measurement<32>;
+/// A 48-byte measurement such as MRTD, MRSEAM or an RTMR.
+pub type Measurement48 = Measurement<48>;
+/// A 64-byte value such as report data.
+pub type Measurement64 = Measurement<64>;
+
+impl<const N: usize> AsRef<[u8]> for Measurement<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Measurement<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Measurement(bytes)
+    }
+}
+
+impl<const N: usize> From<Measurement<N>> for [u8; N] {
+    fn from(measurement: Measurement<N>) -> Self {
+        measurement.0
+    }
+}
+
+/// Lowercase hex without a prefix.
+impl<const N: usize> fmt::Display for Measurement<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+impl<const N: usize> fmt::Debug for Measurement<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Measurement").field(&Hex(&self.0, HexFormat::Plain)).finish()
+    }
+}
+
+/// Parses hex as [`parse_measurement`] does.
+impl<const N: usize> FromStr for Measurement<N> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        parse_measurement(s).map(Measurement)
+    }
+}
+
+macro_rules! measurement_accessors {
+    ($ty:ty { $($field:ident: $len:ident),* $(,)? }) => {
+        impl $ty {
+            $(
+                #[doc = concat!("`", stringify!($field), "` as a [`Measurement`] of [`", stringify!($len), "`] bytes.")]
+                pub fn $field(&self) -> Measurement<$len> {
+                    Measurement(self.$field)
+                }
+            )*
+        }
+    };
+}
+
+measurement_accessors!(TDQuoteBody {
+    mrseam: MRSEAM_LEN,
+    mrsignerseam: MRSIGNERSEAM_LEN,
+    mrtd: MRTD_LEN,
+    mrconfigid: MRCONFIGID_LEN,
+    mrowner: MROWNER_LEN,
+    mrownerconfig: MROWNERCONFIG_LEN,
+    rtmr0: RTMR_LEN,
+    rtmr1: RTMR_LEN,
+    rtmr2: RTMR_LEN,
+    rtmr3: RTMR_LEN,
+    reportdata: REPORT_DATA_LEN,
+});
+
+measurement_accessors!(SgxQuoteBody {
+    mr_enclave: MR_ENCLAVE_LEN,
+    mr_signer: MR_SIGNER_LEN,
+    report_data: REPORT_DATA_LEN,
+});
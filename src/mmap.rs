@@ -0,0 +1,23 @@
+//! Parsing of quote files through a memory map.
+
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::{parse_quote, ParseError, Quote};
+
+impl Quote {
+    /// Parses the quote at the start of the file at `path`, decoding it
+    /// straight from a read-only memory map instead of reading the file into
+    /// a buffer first. Bytes after the quote are ignored.
+    ///
+    /// The file must not be modified while it is being parsed.
+    pub fn parse_mmap(path: &Path) -> Result<Quote, ParseError> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only and dropped before returning; the
+        // caller guarantees the file is not truncated or written meanwhile.
+        let map = unsafe { Mmap::map(&file)? };
+        parse_quote(&map)
+    }
+}
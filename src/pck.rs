@@ -0,0 +1,140 @@
+//! Decoding of the Intel SGX extension carried by PCK leaf certificates.
+
+use alloc::vec::Vec;
+use x509_cert::der::asn1::{ObjectIdentifier, OctetStringRef};
+use x509_cert::der::{Any, Decode, Reader as _, SliceReader};
+use x509_cert::Certificate;
+
+use crate::certification::{first_pem_cert, PCK_CERT_CHAIN_CERT_DATA_TYPE};
+use crate::{CertificationData, ParseError, Quote};
+
+/// `1.2.840.113741.1.13.1`, the SGX extension of PCK certificates.
+const SGX_EXTENSION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1");
+const PPID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.1");
+const TCB: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.2");
+const PCEID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.3");
+const FMSPC: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.4");
+
+/// Platform identifiers and TCB levels from the PCK leaf certificate.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct PckExtensions {
+    pub ppid: [u8; 16],
+    /// Family-model-stepping-platform-custom SKU, used to look up TCB info.
+    pub fmspc: [u8; 6],
+    pub pceid: [u8; 2],
+    /// SVNs of the 16 SGX TCB components.
+    pub tcb: [u8; 16],
+    pub pcesvn: u16,
+    pub cpusvn: [u8; 16],
+}
+
+hex_debug!(PckExtensions { ppid: hex, fmspc: hex, pceid: hex, tcb: hex, pcesvn, cpusvn: hex });
+
+/// Splits a `SEQUENCE { OID, value }` entry of the SGX extension.
+fn entry(any: &Any) -> Result<(ObjectIdentifier, Any), x509_cert::der::Error> {
+    let mut reader = SliceReader::new(any.value())?;
+    let oid = reader.decode()?;
+    let value = reader.decode()?;
+    Ok((oid, value))
+}
+
+fn octets<const N: usize>(value: &Any) -> Option<[u8; N]> {
+    let octets = OctetStringRef::try_from(value).ok()?;
+    octets.as_bytes().try_into().ok()
+}
+
+fn integer(value: &Any) -> Option<u16> {
+    value.decode_as::<u16>().ok()
+}
+
+fn decode_sgx_extension(value: &[u8]) -> Option<PckExtensions> {
+    let mut extensions = PckExtensions::default();
+    for item in Vec::<Any>::from_der(value).ok()? {
+        let (oid, value) = entry(&item).ok()?;
+        match oid {
+            PPID => extensions.ppid = octets(&value)?,
+            PCEID => extensions.pceid = octets(&value)?,
+            FMSPC => extensions.fmspc = octets(&value)?,
+            TCB => {
+                for tcb_item in value.decode_as::<Vec<Any>>().ok()? {
+                    let (oid, value) = entry(&tcb_item).ok()?;
+                    let index = tcb_index(&oid)?;
+                    match index {
+                        1..=16 => extensions.tcb[index - 1] = integer(&value)?.try_into().ok()?,
+                        17 => extensions.pcesvn = integer(&value)?,
+                        18 => extensions.cpusvn = octets(&value)?,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(extensions)
+}
+
+/// The last arc of a TCB entry OID: 1-16 for the component SVNs, 17 for the
+/// PCE SVN and 18 for the CPUSVN. `None` if the OID is not under the TCB arc.
+fn tcb_index(oid: &ObjectIdentifier) -> Option<usize> {
+    let mut arcs = oid.arcs();
+    for expected in TCB.arcs() {
+        if arcs.next()? != expected {
+            return None;
+        }
+    }
+    let index = arcs.next()?;
+    arcs.next().is_none().then_some(index as usize)
+}
+
+impl CertificationData {
+    /// Decodes the SGX extension of the PCK leaf certificate.
+    ///
+    /// Returns `None` unless `cert_data_type` is 5.
+    pub fn pck_extensions(&self) -> Option<Result<PckExtensions, ParseError>> {
+        Some(self.pck_cert_chain()?.and_then(|chain| {
+            let leaf = chain.first().ok_or(ParseError::InvalidCertChain)?;
+            pck_extensions_from_leaf(leaf)
+        }))
+    }
+}
+
+/// The DER value of the SGX extension of the PCK leaf certificate.
+fn sgx_extension(leaf: &[u8]) -> Result<Vec<u8>, ParseError> {
+    let leaf = Certificate::from_der(leaf).map_err(|_| ParseError::InvalidCertChain)?;
+    let extension = leaf
+        .tbs_certificate
+        .extensions
+        .iter()
+        .flatten()
+        .find(|extension| extension.extn_id == SGX_EXTENSION)
+        .ok_or(ParseError::InvalidPckExtension)?;
+    Ok(extension.extn_value.as_bytes().to_vec())
+}
+
+fn pck_extensions_from_leaf(leaf: &[u8]) -> Result<PckExtensions, ParseError> {
+    decode_sgx_extension(&sgx_extension(leaf)?).ok_or(ParseError::InvalidPckExtension)
+}
+
+/// Finds the FMSPC entry of the SGX extension, skipping the other entries.
+fn find_fmspc(value: &[u8]) -> Option<[u8; 6]> {
+    Vec::<Any>::from_der(value).ok()?.iter().find_map(|item| match entry(item).ok()? {
+        (FMSPC, value) => octets(&value),
+        _ => None,
+    })
+}
+
+impl Quote {
+    /// The FMSPC from the PCK leaf certificate, the key for fetching TCB
+    /// info, before any collateral is at hand.
+    ///
+    /// Only the leaf of the PEM chain is decoded and only the FMSPC entry of
+    /// its SGX extension is read. No certificate is validated.
+    pub fn fmspc(&self) -> Result<[u8; 6], ParseError> {
+        let certification_data = self.certification_data();
+        if certification_data.cert_data_type != PCK_CERT_CHAIN_CERT_DATA_TYPE {
+            return Err(ParseError::UnsupportedCertificationDataType(certification_data.cert_data_type));
+        }
+        let leaf = first_pem_cert(&certification_data.data)?;
+        find_fmspc(&sgx_extension(&leaf)?).ok_or(ParseError::InvalidPckExtension)
+    }
+}
@@ -0,0 +1,93 @@
+//! Evaluation of a quote's TD report against a minimum security baseline.
+
+use alloc::vec::Vec;
+
+use crate::{Quote, TDQuoteBody, TeeTcbSvn};
+
+/// `tdattributes` bit that marks a debuggable TD.
+pub const TD_ATTRIBUTES_DEBUG: u64 = 1;
+
+/// Acceptance criteria for the TD report body of a quote.
+///
+/// Unset criteria (`None`, a zero mask or an empty allow list) are not checked.
+#[derive(Clone, Default)]
+pub struct Policy {
+    /// Minimum for each `tee_tcb_svn` byte, compared component by component.
+    pub min_tee_tcb_svn: Option<[u8; 16]>,
+    /// `tdattributes` bits, as a little-endian `u64`, that must all be clear.
+    pub tdattributes_must_be_clear: u64,
+    /// Accepted `mrsignerseam` values.
+    pub allowed_mrsignerseam: Vec<[u8; 48]>,
+    /// Accepted `mrtd` values.
+    pub allowed_mrtd: Vec<[u8; 48]>,
+}
+
+hex_debug!(Policy {
+    min_tee_tcb_svn: opt_hex,
+    tdattributes_must_be_clear,
+    allowed_mrsignerseam: hex_list,
+    allowed_mrtd: hex_list,
+});
+
+/// Outcome of a single policy check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyCheck {
+    pub name: &'static str,
+    pub passed: bool,
+}
+
+/// Outcome of every check a [`Policy`] configures.
+#[derive(Debug, Clone, Default)]
+pub struct PolicyResult {
+    pub checks: Vec<PolicyCheck>,
+}
+
+impl PolicyResult {
+    /// Whether every check passed.
+    pub fn is_accepted(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// The checks that failed.
+    pub fn failed(&self) -> impl Iterator<Item = &PolicyCheck> {
+        self.checks.iter().filter(|check| !check.passed)
+    }
+
+    fn push(&mut self, name: &'static str, passed: bool) {
+        self.checks.push(PolicyCheck { name, passed });
+    }
+}
+
+impl Policy {
+    fn evaluate_td(&self, body: &TDQuoteBody, result: &mut PolicyResult) {
+        if let Some(min) = &self.min_tee_tcb_svn {
+            let passed = body.tee_tcb_svn_components().meets_minimum(&TeeTcbSvn::from_bytes(*min));
+            result.push("tee_tcb_svn", passed);
+        }
+        if self.tdattributes_must_be_clear != 0 {
+            let tdattributes = u64::from_le_bytes(body.tdattributes);
+            result.push("tdattributes", tdattributes & self.tdattributes_must_be_clear == 0);
+        }
+        if !self.allowed_mrsignerseam.is_empty() {
+            result.push("mrsignerseam", self.allowed_mrsignerseam.contains(&body.mrsignerseam));
+        }
+        if !self.allowed_mrtd.is_empty() {
+            result.push("mrtd", self.allowed_mrtd.contains(&body.mrtd));
+        }
+    }
+}
+
+impl Quote {
+    /// Checks the quote's TD report body against `policy`.
+    ///
+    /// A quote without a TD report body fails the `td_quote_body` check and no
+    /// other check is run.
+    pub fn evaluate(&self, policy: &Policy) -> PolicyResult {
+        let mut result = PolicyResult::default();
+        match self.body.td_quote_body.as_tdx() {
+            Some(body) => policy.evaluate_td(body, &mut result),
+            None => result.push("td_quote_body", false),
+        }
+        result
+    }
+}
@@ -0,0 +1,114 @@
+//! Minimal offset-tracking reader over a byte slice.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::cell::RefCell;
+
+use crate::ParseError;
+
+/// Where a named field was read from, as recorded by
+/// [`parse_quote_with_spans`](crate::parse_quote_with_spans).
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldSpan {
+    /// The field name, as used in [`ParseError::UnexpectedEof`].
+    pub name: &'static str,
+    /// Offset of the field from the start of the input.
+    pub offset: usize,
+    pub len: usize,
+}
+
+/// Bounds-checked little-endian reader that never copies more than asked.
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+    /// Offset of `data` within the outermost buffer, for error reporting.
+    base: usize,
+    /// Collects a span for every read, shared with sub-readers.
+    #[cfg(feature = "alloc")]
+    spans: Option<&'a RefCell<Vec<FieldSpan>>>,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Reader {
+            data,
+            offset: 0,
+            base: 0,
+            #[cfg(feature = "alloc")]
+            spans: None,
+        }
+    }
+
+    /// A reader that appends the span of every field it reads to `spans`.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn with_spans(data: &'a [u8], spans: &'a RefCell<Vec<FieldSpan>>) -> Self {
+        Reader { spans: Some(spans), ..Reader::new(data) }
+    }
+
+    pub(crate) fn offset(&self) -> usize {
+        self.base + self.offset
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.data.len() - self.offset
+    }
+
+    /// Returns the next `len` bytes, refusing lengths that run past the buffer.
+    ///
+    /// `field` names what is being read, for the error message.
+    pub(crate) fn read_slice(&mut self, len: usize, field: &'static str) -> Result<&'a [u8], ParseError> {
+        if len > self.remaining() {
+            return Err(ParseError::UnexpectedEof { offset: self.base + self.offset, field });
+        }
+        let bytes = &self.data[self.offset..self.offset + len];
+        #[cfg(feature = "alloc")]
+        if let Some(spans) = self.spans {
+            spans.borrow_mut().push(FieldSpan { name: field, offset: self.offset(), len });
+        }
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    /// Splits off the next `len` bytes as a reader of their own, so that a
+    /// length-prefixed section cannot be read past its declared end.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn sub_reader(&mut self, len: usize, field: &'static str) -> Result<Reader<'a>, ParseError> {
+        let base = self.offset();
+        let data = self.read_slice(len, field)?;
+        Ok(Reader { data, offset: 0, base, spans: self.spans })
+    }
+
+    /// A reader over the start of the next `len` bytes, up to the end of the
+    /// buffer, so that a section can be read no further than its declared
+    /// length without requiring all of it to be present.
+    pub(crate) fn section_prefix(&self, len: usize) -> Reader<'a> {
+        let end = self.offset + len.min(self.remaining());
+        Reader {
+            data: &self.data[self.offset..end],
+            offset: 0,
+            base: self.offset(),
+            #[cfg(feature = "alloc")]
+            spans: self.spans,
+        }
+    }
+
+    /// Returns a reference to the next `N` bytes of the buffer.
+    pub(crate) fn take<const N: usize>(&mut self, field: &'static str) -> Result<&'a [u8; N], ParseError> {
+        let bytes = self.read_slice(N, field)?;
+        Ok(bytes.try_into().expect("slice has length N"))
+    }
+
+    pub(crate) fn read_bytes<const N: usize>(&mut self, field: &'static str) -> Result<[u8; N], ParseError> {
+        self.take(field).copied()
+    }
+
+    pub(crate) fn read_u16(&mut self, field: &'static str) -> Result<u16, ParseError> {
+        self.read_bytes(field).map(u16::from_le_bytes)
+    }
+
+    pub(crate) fn read_u32(&mut self, field: &'static str) -> Result<u32, ParseError> {
+        self.read_bytes(field).map(u32::from_le_bytes)
+    }
+}
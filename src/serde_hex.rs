@@ -0,0 +1,151 @@
+//! Serializes byte arrays as lowercase hex strings in human-readable formats
+//! such as JSON, and as raw bytes in binary formats such as bincode.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::de::{self, Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&hex::encode(self.0))
+        } else {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+}
+
+struct ByteBuf(Vec<u8>);
+
+struct ByteBufVisitor;
+
+impl<'de> Visitor<'de> for ByteBufVisitor {
+    type Value = ByteBuf;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<ByteBuf, E> {
+        Ok(ByteBuf(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+        Ok(ByteBuf(v))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ByteBuf, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(ByteBuf(bytes))
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            hex::decode(s).map(ByteBuf).map_err(D::Error::custom)
+        } else {
+            deserializer.deserialize_byte_buf(ByteBufVisitor)
+        }
+    }
+}
+
+fn to_array<E: de::Error, const N: usize>(bytes: ByteBuf) -> Result<[u8; N], E> {
+    let len = bytes.0.len();
+    bytes.0.try_into().map_err(|_| E::invalid_length(len, &"a fixed-size byte array"))
+}
+
+pub fn serialize<S, const N: usize>(bytes: &[u8; N], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    Bytes(bytes).serialize(serializer)
+}
+
+pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error>
+where
+    D: Deserializer<'de>,
+{
+    to_array(ByteBuf::deserialize(deserializer)?)
+}
+
+/// Same encoding for variable-length byte strings.
+pub mod vec {
+    use alloc::vec::Vec;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{ByteBuf, Bytes};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        Bytes(bytes).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        Ok(ByteBuf::deserialize(deserializer)?.0)
+    }
+}
+
+/// Same encoding for optional byte arrays, with `None` as `null`.
+pub mod option {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{to_array, ByteBuf, Bytes};
+
+    pub fn serialize<S, const N: usize>(bytes: &Option<[u8; N]>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        bytes.as_ref().map(|bytes| Bytes(bytes)).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<Option<[u8; N]>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<ByteBuf>::deserialize(deserializer)?.map(to_array).transpose()
+    }
+}
+
+/// Optional arrays of byte arrays, as a list of hex strings or `null`.
+pub mod option_list {
+    use alloc::vec::Vec;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{to_array, ByteBuf, Bytes};
+
+    pub fn serialize<S, const N: usize, const M: usize>(
+        lists: &Option<[[u8; N]; M]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        lists.as_ref().map(|list| list.iter().map(|bytes| Bytes(bytes)).collect::<Vec<_>>()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, const N: usize, const M: usize>(deserializer: D) -> Result<Option<[[u8; N]; M]>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let lists = match Option::<Vec<ByteBuf>>::deserialize(deserializer)? {
+            Some(lists) => lists,
+            None => return Ok(None),
+        };
+        if lists.len() != M {
+            return Err(D::Error::invalid_length(lists.len(), &"one hex string per array"));
+        }
+        let mut list = [[0; N]; M];
+        for (bytes, buf) in list.iter_mut().zip(lists) {
+            *bytes = to_array(buf)?;
+        }
+        Ok(Some(list))
+    }
+}
@@ -0,0 +1,188 @@
+//! Legacy SGX DCAP version 3 quotes.
+//!
+//! A v3 quote always carries an SGX enclave report body. Its header has a QE
+//! and PCE SVN where v4 has the TEE type and reserved bytes, and its signature
+//! section holds the QE report directly instead of nesting it in type 6
+//! certification data.
+
+use alloc::vec::Vec;
+use uuid::Uuid;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::layout::{QE_REPORT_SIGNATURE_LEN, USER_DATA_LEN};
+use crate::{
+    attestation_key_len, certification, parse_sgx_quote_body, AttestationKeyType, CertificationData, ParseError, QeReport, Reader,
+    SgxQuoteBody,
+};
+
+/// The 48-byte v3 quote header.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SgxQuoteV3Header {
+    pub version: u16,
+    pub attestation_key_type: AttestationKeyType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reserved: [u8; 4],
+    pub qe_svn: u16,
+    pub pce_svn: u16,
+    pub qe_vendor_id: Uuid,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub user_data: [u8; USER_DATA_LEN],
+}
+
+/// A parsed SGX DCAP v3 quote.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(from = "SgxQuoteV3Fields"))]
+pub struct SgxQuoteV3 {
+    pub header: SgxQuoteV3Header,
+    pub body: SgxQuoteBody,
+    pub signature_data_len: u32,
+    /// Raw `r || s` quote signature, 64 bytes for P-256 and 96 for P-384.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec"))]
+    pub signature: Vec<u8>,
+    /// Raw `x || y` attestation public key, the same length as `signature`.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec"))]
+    pub attestation_key: Vec<u8>,
+    pub qe_report: QeReport,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub qe_report_signature: [u8; QE_REPORT_SIGNATURE_LEN],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec"))]
+    pub qe_auth_data: Vec<u8>,
+    pub certification_data: CertificationData,
+    /// The header and body bytes covered by the quote signature. It is not
+    /// serialized, and a deserialized quote re-encodes it from `header` and
+    /// `body`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    signed_data: Vec<u8>,
+}
+
+/// The serialized fields of an [`SgxQuoteV3`].
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct SgxQuoteV3Fields {
+    header: SgxQuoteV3Header,
+    body: SgxQuoteBody,
+    signature_data_len: u32,
+    #[serde(with = "crate::serde_hex::vec")]
+    signature: Vec<u8>,
+    #[serde(with = "crate::serde_hex::vec")]
+    attestation_key: Vec<u8>,
+    qe_report: QeReport,
+    #[serde(with = "crate::serde_hex")]
+    qe_report_signature: [u8; QE_REPORT_SIGNATURE_LEN],
+    #[serde(with = "crate::serde_hex::vec")]
+    qe_auth_data: Vec<u8>,
+    certification_data: CertificationData,
+}
+
+#[cfg(feature = "serde")]
+impl From<SgxQuoteV3Fields> for SgxQuoteV3 {
+    fn from(fields: SgxQuoteV3Fields) -> SgxQuoteV3 {
+        let mut signed_data = Vec::new();
+        fields.header.encode_into(&mut signed_data);
+        fields.body.encode_into(&mut signed_data);
+        SgxQuoteV3 {
+            header: fields.header,
+            body: fields.body,
+            signature_data_len: fields.signature_data_len,
+            signature: fields.signature,
+            attestation_key: fields.attestation_key,
+            qe_report: fields.qe_report,
+            qe_report_signature: fields.qe_report_signature,
+            qe_auth_data: fields.qe_auth_data,
+            certification_data: fields.certification_data,
+            signed_data,
+        }
+    }
+}
+
+impl SgxQuoteV3 {
+    /// The header and body bytes, exactly as read, that the quote signature covers.
+    pub fn signed_data(&self) -> &[u8] {
+        &self.signed_data
+    }
+}
+
+/// `signed_data` carries a copy of the report data, so it is wiped as well.
+#[cfg(feature = "zeroize")]
+impl Drop for SgxQuoteV3 {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.signed_data);
+    }
+}
+
+/// Whether `data` starts with a version 3 quote header, which
+/// [`parse_quote`](crate::parse_quote) rejects with
+/// [`ParseError::UnsupportedVersion`].
+pub fn is_sgx_quote_v3(data: &[u8]) -> bool {
+    data.get(..2) == Some(&3u16.to_le_bytes())
+}
+
+/// Parses an SGX DCAP version 3 quote.
+pub fn parse_sgx_quote_v3(data: &[u8]) -> Result<SgxQuoteV3, ParseError> {
+    let mut reader = Reader::new(data);
+
+    let version = reader.read_u16("version")?;
+    if version != 3 {
+        return Err(ParseError::UnsupportedVersion(version));
+    }
+    let header = SgxQuoteV3Header {
+        version,
+        attestation_key_type: AttestationKeyType::from(reader.read_u16("attestation_key_type")?),
+        reserved: reader.read_bytes("reserved")?,
+        qe_svn: reader.read_u16("qe_svn")?,
+        pce_svn: reader.read_u16("pce_svn")?,
+        qe_vendor_id: Uuid::from_bytes(reader.read_bytes("qe_vendor_id")?),
+        user_data: reader.read_bytes("user_data")?,
+    };
+    let key_len = attestation_key_len(header.attestation_key_type)?;
+    let body = parse_sgx_quote_body(&mut reader)?;
+    let signed_data = data[..reader.offset()].to_vec();
+
+    let signature_data_len = reader.read_u32("signature_data_len")?;
+    let mut section = reader.sub_reader(signature_data_len as usize, "signature_data")?;
+    let signature = section.read_slice(key_len, "signature")?.to_vec();
+    let attestation_key = section.read_slice(key_len, "attestation_key")?.to_vec();
+    let qe_report = parse_sgx_quote_body(&mut section)?;
+    let qe_report_signature = section.read_bytes("qe_report_signature")?;
+    let qe_auth_data_len = section.read_u16("qe_auth_data_len")?;
+    let qe_auth_data = section.read_slice(qe_auth_data_len as usize, "qe_auth_data")?.to_vec();
+
+    Ok(SgxQuoteV3 {
+        header,
+        body,
+        signature_data_len,
+        signature,
+        attestation_key,
+        qe_report,
+        qe_report_signature,
+        qe_auth_data,
+        certification_data: certification::parse_certification_data(&mut section)?,
+        signed_data,
+    })
+}
+
+hex_debug!(SgxQuoteV3Header {
+    version,
+    attestation_key_type,
+    reserved: hex,
+    qe_svn,
+    pce_svn,
+    qe_vendor_id,
+    user_data: hex,
+});
+
+hex_debug!(SgxQuoteV3 {
+    header,
+    body,
+    signature_data_len,
+    signature: hex,
+    attestation_key: hex,
+    qe_report,
+    qe_report_signature: hex,
+    qe_auth_data: hex,
+    certification_data,
+    signed_data: hex,
+});
@@ -0,0 +1,58 @@
+//! Parsing of quotes pulled incrementally from an `io::Read`.
+
+use std::io::Read;
+
+use crate::layout::HEADER_LEN;
+use crate::{check_body_size, check_version, parse_quote, v4_body_type, ParseError, Quote, QuoteBodyType, TEEType};
+
+/// Appends exactly `len` bytes from `r` to `buf`.
+///
+/// The buffer grows with the bytes actually read rather than with `len`, so a
+/// corrupted length prefix cannot force a huge allocation up front.
+fn pull<R: Read>(r: &mut R, buf: &mut Vec<u8>, len: u64, field: &'static str) -> Result<(), ParseError> {
+    let start = buf.len();
+    let read = r.take(len).read_to_end(buf)?;
+    if (read as u64) < len {
+        return Err(ParseError::UnexpectedEof { offset: start + read, field });
+    }
+    Ok(())
+}
+
+fn pull_u16<R: Read>(r: &mut R, buf: &mut Vec<u8>, field: &'static str) -> Result<u16, ParseError> {
+    pull(r, buf, 2, field)?;
+    Ok(u16::from_le_bytes([buf[buf.len() - 2], buf[buf.len() - 1]]))
+}
+
+fn pull_u32<R: Read>(r: &mut R, buf: &mut Vec<u8>, field: &'static str) -> Result<u32, ParseError> {
+    pull(r, buf, 4, field)?;
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&buf[buf.len() - 4..]);
+    Ok(u32::from_le_bytes(bytes))
+}
+
+impl Quote {
+    /// Reads a single quote from `r`, pulling only as many bytes as its length
+    /// fields call for and leaving anything after it unread.
+    pub fn from_reader<R: Read>(mut r: R) -> Result<Quote, ParseError> {
+        let mut buf = Vec::new();
+
+        let version = pull_u16(&mut r, &mut buf, "version")?;
+        check_version(version)?;
+        pull(&mut r, &mut buf, HEADER_LEN as u64 - 2, "header")?;
+        let body_len = if version == 4 {
+            let tee_type = TEEType::from(u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]));
+            v4_body_type(tee_type)?.body_size().unwrap_or_default() as u64
+        } else {
+            let td_quote_body_type = QuoteBodyType::from(pull_u16(&mut r, &mut buf, "td_quote_body_type")?);
+            let size = pull_u32(&mut r, &mut buf, "size")?;
+            check_body_size(td_quote_body_type, size)?;
+            size as u64
+        };
+        pull(&mut r, &mut buf, body_len, "td_quote_body")?;
+
+        let signature_data_len = pull_u32(&mut r, &mut buf, "signature_data_len")?;
+        pull(&mut r, &mut buf, signature_data_len as u64, "signature_data")?;
+
+        parse_quote(&buf)
+    }
+}
@@ -0,0 +1,97 @@
+//! Compact view of the fields most callers log or check.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::layout::{MRSEAM_LEN, MRTD_LEN, REPORT_DATA_LEN, RTMR_LEN};
+use crate::{Quote, QuoteBodyKind, TEEType};
+
+/// The header and measurement fields of a quote, without the signature
+/// section. TD-only fields are `None` for SGX report bodies.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "zeroize", derive(Zeroize, ZeroizeOnDrop))]
+pub struct QuoteSummary {
+    pub version: u16,
+    #[cfg_attr(feature = "zeroize", zeroize(skip))]
+    pub tee_type: TEEType,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option"))]
+    pub mrtd: Option<[u8; MRTD_LEN]>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option"))]
+    pub mrseam: Option<[u8; MRSEAM_LEN]>,
+    /// `rtmr0` to `rtmr3`.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option_list"))]
+    pub rtmrs: Option<[[u8; RTMR_LEN]; 4]>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub reportdata: [u8; REPORT_DATA_LEN],
+    /// The TD or enclave debug attribute.
+    pub debug: bool,
+}
+
+hex_debug!(QuoteSummary {
+    version,
+    tee_type,
+    mrtd: opt_hex,
+    mrseam: opt_hex,
+    rtmrs: opt_hex_list,
+    reportdata: hex,
+    debug,
+});
+
+impl Quote {
+    /// Extracts a [`QuoteSummary`].
+    pub fn summary(&self) -> QuoteSummary {
+        let (version, tee_type) = (self.header.version, self.header.tee_type);
+        match &self.body.td_quote_body {
+            QuoteBodyKind::Tdx(body) => QuoteSummary {
+                version,
+                tee_type,
+                mrtd: Some(body.mrtd),
+                mrseam: Some(body.mrseam),
+                rtmrs: Some(body.rtmrs()),
+                reportdata: body.reportdata,
+                debug: body.is_debug(),
+            },
+            QuoteBodyKind::Sgx(body) => QuoteSummary {
+                version,
+                tee_type,
+                mrtd: None,
+                mrseam: None,
+                rtmrs: None,
+                reportdata: body.report_data,
+                debug: body.attributes_decoded().debug,
+            },
+        }
+    }
+
+    /// Label pairs for tagging metrics, always the same keys in the same
+    /// order: `mrtd`, `mrseam`, `tee_type`, `version` and `debug`.
+    ///
+    /// Measurements are lowercase hex, empty for SGX report bodies, and
+    /// `tee_type` is `sgx`, `tdx` or the raw value in hex.
+    pub fn to_labels(&self) -> Vec<(String, String)> {
+        let summary = self.summary();
+        let hex_or_empty = |value: Option<[u8; MRTD_LEN]>| value.map(hex::encode).unwrap_or_default();
+        let tee_type = match summary.tee_type {
+            TEEType::SGX => "sgx".to_string(),
+            TEEType::TDX => "tdx".to_string(),
+            TEEType::Unknown(tee_type) => format!("0x{:08x}", tee_type),
+        };
+        [
+            ("mrtd", hex_or_empty(summary.mrtd)),
+            ("mrseam", hex_or_empty(summary.mrseam)),
+            ("tee_type", tee_type),
+            ("version", summary.version.to_string()),
+            ("debug", summary.debug.to_string()),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+    }
+}
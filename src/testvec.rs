@@ -0,0 +1,179 @@
+//! Deterministic synthetic quotes for testing code that consumes quotes.
+
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::Signature;
+/// The key type accepted by [`QuoteBuilder::signing_key`], re-exported so
+/// callers need not depend on the same `p256` version.
+pub use p256::ecdsa::SigningKey;
+use sha2::{Digest, Sha256};
+
+use crate::layout::*;
+use crate::{
+    AttestationKeyType, CertificationData, Quote, QuoteBody, QuoteBodyKind, QuoteBodyType, QuoteHeader, QuoteSignatureData,
+    SgxQuoteBody, TDQuoteBody, TEEType, INTEL_QE_VENDOR_ID, PCK_CERT_CHAIN_CERT_DATA_TYPE,
+};
+
+/// Type and size in front of certification data.
+const CERT_DATA_HEADER_LEN: usize = 6;
+/// The `u16` in front of the QE authentication data.
+const QE_AUTH_DATA_LEN_LEN: usize = 2;
+
+/// Builds the bytes of a TDX quote with chosen field values.
+///
+/// Starts from a v4 TDX 1.0 quote from the Intel QE vendor with every
+/// measurement zeroed. Without a signing key the quote signature and
+/// attestation key are zero. The QE report only carries the report data
+/// binding the attestation key, and the QE report signature is zero, so
+/// `verify_qe_report_binding` passes but the PCK chain checks do not.
+#[derive(Clone)]
+pub struct QuoteBuilder {
+    header: QuoteHeader,
+    body: TDQuoteBody,
+    signing_key: Option<SigningKey>,
+    qe_auth_data: Vec<u8>,
+    certification_data: CertificationData,
+}
+
+impl Default for QuoteBuilder {
+    fn default() -> Self {
+        QuoteBuilder {
+            header: QuoteHeader { qe_vendor_id: INTEL_QE_VENDOR_ID, ..QuoteHeader::default() },
+            body: TDQuoteBody::default(),
+            signing_key: None,
+            qe_auth_data: Vec::new(),
+            certification_data: CertificationData { cert_data_type: PCK_CERT_CHAIN_CERT_DATA_TYPE, size: 0, data: Vec::new() },
+        }
+    }
+}
+
+impl QuoteBuilder {
+    pub fn new() -> Self {
+        QuoteBuilder::default()
+    }
+
+    /// Replaces the whole header. Its TEE type and attestation key type are
+    /// overridden with TDX and ECDSA-256-with-P-256 on build.
+    pub fn header(mut self, header: QuoteHeader) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Quote version, 4 or 5. A v5 quote carries a body descriptor.
+    pub fn version(mut self, version: u16) -> Self {
+        self.header.version = version;
+        self
+    }
+
+    /// Replaces the whole TD report body. A body with `tee_tcb_svn_2` or
+    /// `mrservicetd` set is built as a TDX 1.5 body in a v5 quote.
+    pub fn td_body(mut self, body: TDQuoteBody) -> Self {
+        self.body = body;
+        self
+    }
+
+    pub fn mrtd(mut self, mrtd: [u8; MRTD_LEN]) -> Self {
+        self.body.mrtd = mrtd;
+        self
+    }
+
+    /// Sets `rtmr0` to `rtmr3`.
+    pub fn rtmrs(mut self, rtmrs: [[u8; RTMR_LEN]; 4]) -> Self {
+        [self.body.rtmr0, self.body.rtmr1, self.body.rtmr2, self.body.rtmr3] = rtmrs;
+        self
+    }
+
+    pub fn reportdata(mut self, reportdata: [u8; REPORT_DATA_LEN]) -> Self {
+        self.body.reportdata = reportdata;
+        self
+    }
+
+    pub fn tdattributes(mut self, tdattributes: [u8; TD_ATTRIBUTES_LEN]) -> Self {
+        self.body.tdattributes = tdattributes;
+        self
+    }
+
+    pub fn seamattributes(mut self, seamattributes: [u8; SEAM_ATTRIBUTES_LEN]) -> Self {
+        self.body.seamattributes = seamattributes;
+        self
+    }
+
+    pub fn xfam(mut self, xfam: [u8; XFAM_LEN]) -> Self {
+        self.body.xfam = xfam;
+        self
+    }
+
+    /// Signs the quote with `key`, which becomes the attestation key.
+    /// ECDSA signatures are derived per RFC 6979, so output stays deterministic.
+    pub fn signing_key(mut self, key: SigningKey) -> Self {
+        self.signing_key = Some(key);
+        self
+    }
+
+    pub fn qe_auth_data(mut self, qe_auth_data: Vec<u8>) -> Self {
+        self.qe_auth_data = qe_auth_data;
+        self
+    }
+
+    /// Certification data nested in the QE report certification data, by
+    /// default an empty PCK certificate chain (type 5).
+    pub fn certification_data(mut self, cert_data_type: u16, data: Vec<u8>) -> Self {
+        self.certification_data = CertificationData { cert_data_type, size: data.len() as u32, data };
+        self
+    }
+
+    /// Encodes the quote.
+    pub fn build(&self) -> Vec<u8> {
+        let mut header = self.header.clone();
+        header.attestation_key_type = AttestationKeyType::EcdsaP256;
+        header.tee_type = TEEType::TDX;
+        let mut body = self.body.clone();
+        let td_quote_body_type = if body.tee_tcb_svn_2.is_some() || body.mrservicetd.is_some() {
+            body.tee_tcb_svn_2.get_or_insert([0; TEE_TCB_SVN_LEN]);
+            body.mrservicetd.get_or_insert([0; MRSERVICETD_LEN]);
+            header.version = 5;
+            QuoteBodyType::Tdx15
+        } else {
+            QuoteBodyType::Tdx10
+        };
+        let body = QuoteBody {
+            td_quote_body_type,
+            size: td_quote_body_type.body_size().unwrap_or_default() as u32,
+            td_quote_body: QuoteBodyKind::Tdx(body),
+        };
+
+        let signed_data = crate::encode::encode_signed_data(&header, &body);
+
+        let (signature, attestation_key) = match &self.signing_key {
+            Some(key) => {
+                let signature: Signature = key.sign(&signed_data);
+                let point = key.verifying_key().to_encoded_point(false);
+                // Drop the SEC1 tag to get the raw `x || y` key.
+                (signature.to_bytes().to_vec(), point.as_bytes()[1..].to_vec())
+            }
+            None => (vec![0; 64], vec![0; 64]),
+        };
+
+        let mut qe_report = SgxQuoteBody::default();
+        let binding = Sha256::new().chain_update(&attestation_key).chain_update(&self.qe_auth_data).finalize();
+        qe_report.report_data[..binding.len()].copy_from_slice(&binding);
+
+        let qe_cert_data_size = SGX_REPORT_LEN
+            + QE_REPORT_SIGNATURE_LEN
+            + QE_AUTH_DATA_LEN_LEN
+            + self.qe_auth_data.len()
+            + CERT_DATA_HEADER_LEN
+            + self.certification_data.data.len();
+        let signature = QuoteSignatureData {
+            signature_data_len: (signature.len() + attestation_key.len() + CERT_DATA_HEADER_LEN + qe_cert_data_size) as u32,
+            signature,
+            attestation_key,
+            qe_cert_data_type: crate::QE_REPORT_CERT_DATA_TYPE,
+            qe_cert_data_size: qe_cert_data_size as u32,
+            qe_report,
+            qe_report_signature: [0; QE_REPORT_SIGNATURE_LEN],
+            qe_auth_data: self.qe_auth_data.clone(),
+            certification_data: self.certification_data.clone(),
+        };
+        Quote { header, body, signature, signed_data }.to_bytes()
+    }
+}
@@ -0,0 +1,375 @@
+//! Cryptographic checks over a parsed quote.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use core::time::Duration;
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use x509_cert::crl::CertificateList;
+use x509_cert::der::asn1::{BitString, ObjectIdentifier};
+use x509_cert::der::{Decode, Encode};
+use x509_cert::serial_number::SerialNumber;
+use x509_cert::Certificate;
+
+use crate::layout::SGX_REPORT_LEN;
+use crate::{AttestationKeyType, Quote, QuoteSignatureData};
+
+/// DER encoding of the Intel SGX Root CA, the trust anchor of PCK certificate chains.
+pub const INTEL_SGX_ROOT_CA: &[u8] = include_bytes!("../certs/Intel_SGX_Root_CA.der");
+
+/// `ecdsa-with-SHA256`, the only signature algorithm used in PCK certificate chains.
+const ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+
+/// Errors returned when a quote fails verification.
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyError {
+    /// The QE report data does not commit to the attestation key and QE authentication data.
+    #[error("QE report data does not match the attestation key and QE authentication data")]
+    QeReportBindingMismatch,
+    /// The quote is signed with an attestation key type other than ECDSA-256-with-P-256.
+    #[error("only ECDSA-256-with-P-256 quote signatures can be verified")]
+    UnsupportedAttestationKeyType,
+    /// The attestation key is not a valid P-256 point.
+    #[error("attestation key is not a valid P-256 public key")]
+    InvalidAttestationKey,
+    /// The quote signature does not verify under the attestation key.
+    #[error("quote signature verification failed")]
+    InvalidQuoteSignature,
+    /// The QE report signature does not verify under the PCK leaf certificate key.
+    #[error("QE report signature verification failed")]
+    InvalidQeReportSignature,
+    /// The certification data does not carry a PCK certificate chain.
+    #[error("certification data has no PCK certificate chain")]
+    MissingPckCertChain,
+    /// A certificate in the chain, or the root CA, could not be decoded.
+    #[error("malformed X.509 certificate")]
+    InvalidCertificate,
+    /// A certificate is not signed by the next one in the chain.
+    #[error("PCK certificate chain signature verification failed")]
+    CertChainSignatureMismatch,
+    /// A certificate in the chain is not valid at the time of verification.
+    #[error("certificate outside its validity period")]
+    CertificateExpired,
+    /// The TCB info is for a different platform than the PCK certificate.
+    #[cfg(feature = "collateral")]
+    #[error("TCB info FMSPC does not match the PCK certificate")]
+    FmspcMismatch,
+    /// The TCB info is for a different TEE type or PCE than the quote.
+    #[cfg(feature = "collateral")]
+    #[error("TCB info id or PCE ID does not match the quote")]
+    TcbInfoMismatch,
+    /// The TDX module signer or attributes do not match the TCB info, or
+    /// the TCB info has no identity for the module's major version.
+    #[cfg(feature = "collateral")]
+    #[error("TDX module does not match the TCB info")]
+    TdxModuleMismatch,
+    /// The quote does not meet any TCB level of the collateral.
+    #[cfg(feature = "collateral")]
+    #[error("no matching TCB level")]
+    TcbLevelNotFound,
+    /// A CRL could not be decoded.
+    #[error("malformed CRL")]
+    InvalidCrl,
+    /// A CRL is not signed by the CA whose certificates it revokes.
+    #[error("CRL signature verification failed")]
+    CrlSignatureMismatch,
+    /// A certificate in the PCK chain is listed in its issuer's CRL.
+    #[error("certificate has been revoked")]
+    CertificateRevoked,
+    /// The QE report does not match the QE identity.
+    #[cfg(feature = "collateral")]
+    #[error("malformed collateral")]
+    InvalidCollateral(#[source] crate::ParseError),
+
+    #[cfg(feature = "collateral")]
+    #[error("collateral signature verification failed")]
+    InvalidCollateralSignature,
+
+    #[cfg(feature = "collateral")]
+    #[error("collateral outside its validity period")]
+    CollateralExpired,
+
+    #[cfg(feature = "collateral")]
+    #[error("QE report does not match the QE identity")]
+    QeIdentityMismatch,
+    /// The platform or QE TCB status is out of date or revoked.
+    #[cfg(feature = "collateral")]
+    #[error("TCB status {0:?} is not acceptable")]
+    TcbStatusRejected(crate::collateral::TcbStatus),
+    /// The quote bytes could not be parsed.
+    #[cfg(feature = "collateral")]
+    #[error("malformed quote")]
+    Parse(#[source] crate::ParseError),
+}
+
+/// Builds a P-256 verifying key from the attestation key of `signature`.
+fn p256_verifying_key(signature: &QuoteSignatureData) -> Option<VerifyingKey> {
+    if signature.attestation_key.len() != 64 {
+        return None;
+    }
+    VerifyingKey::from_sec1_bytes(&signature.attestation_pubkey_sec1()).ok()
+}
+
+/// Checks that `tbs` is signed with ECDSA-P256/SHA-256 by the key of `issuer`.
+fn verify_signed_by(tbs: &[u8], algorithm: ObjectIdentifier, signature: &BitString, issuer: &Certificate) -> Option<()> {
+    if algorithm != ECDSA_WITH_SHA256 {
+        return None;
+    }
+    let public_key = issuer.tbs_certificate.subject_public_key_info.subject_public_key.raw_bytes();
+    let key = VerifyingKey::from_sec1_bytes(public_key).ok()?;
+    let signature = Signature::from_der(signature.raw_bytes()).ok()?;
+    key.verify(tbs, &signature).ok()
+}
+
+/// Checks that `cert` is signed with ECDSA-P256/SHA-256 by the key of `issuer`.
+fn verify_issued_by(cert: &Certificate, issuer: &Certificate) -> Result<(), VerifyError> {
+    let tbs = cert.tbs_certificate.to_der().map_err(|_| VerifyError::InvalidCertificate)?;
+    verify_signed_by(&tbs, cert.signature_algorithm.oid, &cert.signature, issuer).ok_or(VerifyError::CertChainSignatureMismatch)
+}
+
+const CRL_PEM_BEGIN: &str = "-----BEGIN X509 CRL-----";
+const CRL_PEM_END: &str = "-----END X509 CRL-----";
+
+/// Decodes a DER or PEM CRL without checking its signature.
+fn decode_crl_unverified(data: &[u8]) -> Result<CertificateList, VerifyError> {
+    match core::str::from_utf8(data).ok().and_then(|text| text.trim().strip_prefix(CRL_PEM_BEGIN)) {
+        Some(body) => {
+            let end = body.find(CRL_PEM_END).ok_or(VerifyError::InvalidCrl)?;
+            let base64: String = body[..end].chars().filter(|c| !c.is_ascii_whitespace()).collect();
+            let der = STANDARD.decode(base64).map_err(|_| VerifyError::InvalidCrl)?;
+            CertificateList::from_der(&der)
+        }
+        None => CertificateList::from_der(data),
+    }
+    .map_err(|_| VerifyError::InvalidCrl)
+}
+
+/// Decodes a DER or PEM CRL and checks that it is signed by `issuer`.
+fn decode_crl(data: &[u8], issuer: &Certificate) -> Result<CertificateList, VerifyError> {
+    let crl = decode_crl_unverified(data)?;
+    let tbs = crl.tbs_cert_list.to_der().map_err(|_| VerifyError::InvalidCrl)?;
+    verify_signed_by(&tbs, crl.signature_algorithm.oid, &crl.signature, issuer).ok_or(VerifyError::CrlSignatureMismatch)?;
+    Ok(crl)
+}
+
+fn is_revoked(crl: &CertificateList, serial: &SerialNumber) -> bool {
+    crl.tbs_cert_list.revoked_certificates.iter().flatten().any(|revoked| &revoked.serial_number == serial)
+}
+
+fn check_validity(cert: &Certificate, now: Duration) -> Result<(), VerifyError> {
+    let validity = &cert.tbs_certificate.validity;
+    if now < validity.not_before.to_unix_duration() || now > validity.not_after.to_unix_duration() {
+        return Err(VerifyError::CertificateExpired);
+    }
+    Ok(())
+}
+
+/// PCK chain and revocation checks against a root CA, with the root CA and
+/// CRLs decoded once, for verifying many quotes.
+///
+/// An intermediate CA that passed its checks is remembered, so quotes from
+/// the same CA only pay for their leaf certificate.
+#[cfg(feature = "collateral")]
+pub(crate) struct ChainVerifier<'a> {
+    root_der: &'a [u8],
+    root: Certificate,
+    root_crl: CertificateList,
+    pck_crl: CertificateList,
+    pck_crl_tbs: Vec<u8>,
+    verified_intermediate: Option<Vec<u8>>,
+}
+
+#[cfg(feature = "collateral")]
+impl<'a> ChainVerifier<'a> {
+    pub(crate) fn new(root_ca: &'a [u8], pck_crl: &[u8], root_crl: &[u8]) -> Result<Self, VerifyError> {
+        let root = Certificate::from_der(root_ca).map_err(|_| VerifyError::InvalidCertificate)?;
+        let root_crl = decode_crl(root_crl, &root)?;
+        // The PCK CRL issuer is the intermediate of each quote's chain, so
+        // its signature is checked when an intermediate is first seen.
+        let pck_crl = decode_crl_unverified(pck_crl)?;
+        let pck_crl_tbs = pck_crl.tbs_cert_list.to_der().map_err(|_| VerifyError::InvalidCrl)?;
+        Ok(ChainVerifier { root_der: root_ca, root, root_crl, pck_crl, pck_crl_tbs, verified_intermediate: None })
+    }
+
+    /// Whether this verifier reaches the same verdict as
+    /// [`Quote::verify_cert_chain_at`] and the revocation checks for `chain`:
+    /// a leaf and intermediate, optionally followed by the root.
+    pub(crate) fn handles(&self, chain: &[Vec<u8>]) -> bool {
+        match chain {
+            [_, _] => true,
+            [_, _, root] => root == self.root_der,
+            _ => false,
+        }
+    }
+
+    /// Checks a chain accepted by [`ChainVerifier::handles`] at `now`.
+    pub(crate) fn verify(&mut self, chain: &[Vec<u8>], now: Duration) -> Result<(), VerifyError> {
+        let decode = |der: &[u8]| Certificate::from_der(der).map_err(|_| VerifyError::InvalidCertificate);
+        let (leaf, intermediate) = (decode(&chain[0])?, decode(&chain[1])?);
+        verify_issued_by(&leaf, &intermediate)?;
+
+        let known = self.verified_intermediate.as_deref() == Some(chain[1].as_slice());
+        if !known {
+            verify_issued_by(&intermediate, &self.root)?;
+        }
+        [&leaf, &intermediate, &self.root].into_iter().try_for_each(|cert| check_validity(cert, now))?;
+        if !known {
+            verify_signed_by(&self.pck_crl_tbs, self.pck_crl.signature_algorithm.oid, &self.pck_crl.signature, &intermediate)
+                .ok_or(VerifyError::CrlSignatureMismatch)?;
+            if is_revoked(&self.root_crl, &intermediate.tbs_certificate.serial_number) {
+                return Err(VerifyError::CertificateRevoked);
+            }
+            self.verified_intermediate = Some(chain[1].clone());
+        }
+        if is_revoked(&self.pck_crl, &leaf.tbs_certificate.serial_number) {
+            return Err(VerifyError::CertificateRevoked);
+        }
+        Ok(())
+    }
+}
+
+/// Checks the TCB signing certificate, the first of a PEM
+/// `TCB-Info-Issuer-Chain`, against `root_ca` and its CRL at `now`, and
+/// returns its key.
+#[cfg(feature = "collateral")]
+pub(crate) fn tcb_signing_key(chain: &[u8], root_ca: &[u8], root_crl: &[u8], now: Duration) -> Result<VerifyingKey, VerifyError> {
+    let signer = crate::certification::first_pem_cert(chain).map_err(|_| VerifyError::InvalidCertificate)?;
+    let decode = |der: &[u8]| Certificate::from_der(der).map_err(|_| VerifyError::InvalidCertificate);
+    let (signer, root) = (decode(&signer)?, decode(root_ca)?);
+    verify_issued_by(&signer, &root)?;
+    [&signer, &root].into_iter().try_for_each(|cert| check_validity(cert, now))?;
+    if is_revoked(&decode_crl(root_crl, &root)?, &signer.tbs_certificate.serial_number) {
+        return Err(VerifyError::CertificateRevoked);
+    }
+    let public_key = signer.tbs_certificate.subject_public_key_info.subject_public_key.raw_bytes();
+    VerifyingKey::from_sec1_bytes(public_key).map_err(|_| VerifyError::InvalidCertificate)
+}
+
+/// Checks a hex `r || s` ECDSA-P256/SHA-256 signature of PCS collateral over `body`.
+#[cfg(feature = "collateral")]
+pub(crate) fn verify_collateral_signature(key: &VerifyingKey, body: &[u8], signature: &str) -> Result<(), VerifyError> {
+    let mut bytes = [0; 64];
+    hex::decode_to_slice(signature, &mut bytes).map_err(|_| VerifyError::InvalidCollateralSignature)?;
+    let signature = Signature::from_slice(&bytes).map_err(|_| VerifyError::InvalidCollateralSignature)?;
+    key.verify(body, &signature).map_err(|_| VerifyError::InvalidCollateralSignature)
+}
+
+impl Quote {
+    /// Verifies the PCK certificate chain up to `root_ca`, a DER certificate
+    /// such as [`INTEL_SGX_ROOT_CA`], at the current system time.
+    #[cfg(feature = "std")]
+    pub fn verify_cert_chain(&self, root_ca: &[u8]) -> Result<(), VerifyError> {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+        self.verify_cert_chain_at(root_ca, now)
+    }
+
+    /// Verifies the PCK certificate chain up to `root_ca` at `now`, given as
+    /// the time since the Unix epoch.
+    ///
+    /// Each certificate must be signed by the next one and the chain must end
+    /// at `root_ca`, which is appended if the quote omits it. Every certificate,
+    /// including the root, must be valid at `now`.
+    pub fn verify_cert_chain_at(&self, root_ca: &[u8], now: Duration) -> Result<(), VerifyError> {
+        let mut chain = match self.certification_data().pck_cert_chain() {
+            Some(Ok(chain)) if !chain.is_empty() => chain,
+            _ => return Err(VerifyError::MissingPckCertChain),
+        };
+        if chain.last().map(Vec::as_slice) != Some(root_ca) {
+            chain.push(root_ca.to_vec());
+        }
+        let certs = chain
+            .iter()
+            .map(|der| Certificate::from_der(der).map_err(|_| VerifyError::InvalidCertificate))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for pair in certs.windows(2) {
+            verify_issued_by(&pair[0], &pair[1])?;
+        }
+        certs.iter().try_for_each(|cert| check_validity(cert, now))
+    }
+
+    /// Checks the PCK certificate chain against Intel PCS revocation lists.
+    ///
+    /// `pck_crl` is the CRL of the PCK platform or processor CA and must not
+    /// list the PCK leaf; `root_crl` is the Intel SGX Root CA CRL and must not
+    /// list the intermediate CA. Each CRL may be DER or PEM and must be signed
+    /// by its CA, taken from the chain or [`INTEL_SGX_ROOT_CA`] if the chain
+    /// omits the root. CRL validity periods are not checked.
+    pub fn check_revocation(&self, pck_crl: &[u8], root_crl: &[u8]) -> Result<(), VerifyError> {
+        self.check_revocation_with_root_ca(pck_crl, root_crl, INTEL_SGX_ROOT_CA)
+    }
+
+    /// [`check_revocation`](Quote::check_revocation) with `root_ca` as the
+    /// root CA of chains that omit it.
+    pub(crate) fn check_revocation_with_root_ca(&self, pck_crl: &[u8], root_crl: &[u8], root_ca: &[u8]) -> Result<(), VerifyError> {
+        let chain = match self.certification_data().pck_cert_chain() {
+            Some(Ok(chain)) if chain.len() >= 2 => chain,
+            _ => return Err(VerifyError::MissingPckCertChain),
+        };
+        let root = chain.get(2).map_or(root_ca, Vec::as_slice);
+        let decode = |der: &[u8]| Certificate::from_der(der).map_err(|_| VerifyError::InvalidCertificate);
+        let (leaf, intermediate, root) = (decode(&chain[0])?, decode(&chain[1])?, decode(root)?);
+
+        let pck_crl = decode_crl(pck_crl, &intermediate)?;
+        let root_crl = decode_crl(root_crl, &root)?;
+        if is_revoked(&pck_crl, &leaf.tbs_certificate.serial_number)
+            || is_revoked(&root_crl, &intermediate.tbs_certificate.serial_number)
+        {
+            return Err(VerifyError::CertificateRevoked);
+        }
+        Ok(())
+    }
+
+    /// SHA-256 over [`signed_data`](Quote::signed_data), the digest the
+    /// attestation key signs.
+    pub fn report_digest(&self) -> [u8; 32] {
+        Sha256::digest(&self.signed_data).into()
+    }
+
+    /// Verifies the ECDSA-P256 quote signature over the header and report
+    /// body with the attestation key carried in the quote.
+    pub fn verify_signature(&self) -> Result<(), VerifyError> {
+        if self.header.attestation_key_type != AttestationKeyType::EcdsaP256 {
+            return Err(VerifyError::UnsupportedAttestationKeyType);
+        }
+        let key = p256_verifying_key(&self.signature).ok_or(VerifyError::InvalidAttestationKey)?;
+        let signature = Signature::from_slice(&self.signature.signature).map_err(|_| VerifyError::InvalidQuoteSignature)?;
+        key.verify(&self.signed_data, &signature).map_err(|_| VerifyError::InvalidQuoteSignature)
+    }
+
+    /// Verifies the ECDSA-P256 QE report signature with the key of the PCK
+    /// leaf certificate, linking the QE to the certificate chain.
+    pub fn verify_qe_report_signature(&self) -> Result<(), VerifyError> {
+        let leaf = match self.certification_data().pck_cert_chain() {
+            Some(Ok(chain)) if !chain.is_empty() => Certificate::from_der(&chain[0]).map_err(|_| VerifyError::InvalidCertificate)?,
+            _ => return Err(VerifyError::MissingPckCertChain),
+        };
+        let public_key = leaf.tbs_certificate.subject_public_key_info.subject_public_key.raw_bytes();
+        let key = VerifyingKey::from_sec1_bytes(public_key).map_err(|_| VerifyError::InvalidCertificate)?;
+        let signature =
+            Signature::from_slice(&self.signature.qe_report_signature).map_err(|_| VerifyError::InvalidQeReportSignature)?;
+        let mut qe_report = Vec::with_capacity(SGX_REPORT_LEN);
+        self.signature.qe_report.encode_into(&mut qe_report);
+        key.verify(&qe_report, &signature).map_err(|_| VerifyError::InvalidQeReportSignature)
+    }
+
+    /// Checks that the QE report binds the attestation key.
+    ///
+    /// The first 32 bytes of the QE report data must be
+    /// `SHA256(attestation_key || qe_auth_data)` and the remaining 32 bytes zero.
+    pub fn verify_qe_report_binding(&self) -> Result<(), VerifyError> {
+        let mut hasher = Sha256::new();
+        hasher.update(&self.signature.attestation_key);
+        hasher.update(&self.signature.qe_auth_data);
+        let expected = hasher.finalize();
+
+        let report_data = &self.signature.qe_report.report_data;
+        if report_data[..32] != expected[..] || report_data[32..].iter().any(|&b| b != 0) {
+            return Err(VerifyError::QeReportBindingMismatch);
+        }
+        Ok(())
+    }
+}
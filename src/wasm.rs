@@ -0,0 +1,13 @@
+//! JavaScript binding for parsing quotes in the browser.
+
+use wasm_bindgen::prelude::*;
+
+use crate::parse_quote;
+
+/// Parses a quote and returns its JSON serialization, or the parse error as a
+/// JavaScript string.
+#[wasm_bindgen]
+pub fn parse_quote_json(bytes: &[u8]) -> Result<String, JsValue> {
+    let quote = parse_quote(bytes).map_err(|err| JsValue::from_str(&err.to_string()))?;
+    serde_json::to_string(&quote).map_err(|err| JsValue::from_str(&err.to_string()))
+}
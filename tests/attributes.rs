@@ -0,0 +1,15 @@
+use tdx_quote_parser::TdAttributes;
+
+#[test]
+fn td_attributes_cover_every_bit() {
+    let all = TdAttributes::from_bytes([0xff; 8]);
+    assert!(all.debug && all.septve_disable && all.migratable && all.pks && all.kl && all.perfmon);
+    assert_eq!(all.tud_reserved, 0x7f);
+    assert_eq!(all.sec_reserved, 0xfffff);
+    assert_eq!(all.other_reserved, 0x7fff_ffff);
+
+    let migratable = TdAttributes::from_bytes((1u64 << 29).to_le_bytes());
+    assert!(migratable.migratable);
+    assert_eq!(migratable.sec_reserved, 0);
+    assert!(!migratable.septve_disable && !migratable.pks);
+}
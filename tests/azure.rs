@@ -0,0 +1,26 @@
+mod common;
+
+use base64::Engine;
+use tdx_quote_parser::azure::parse_maa_response;
+use tdx_quote_parser::{parse_quote, ParseError};
+
+use common::quote_bytes;
+
+#[test]
+fn parses_padded_and_unpadded_base64url_quotes() {
+    let bytes = quote_bytes();
+    let expected = parse_quote(&bytes).unwrap();
+    for engine in [base64::engine::general_purpose::URL_SAFE, base64::engine::general_purpose::URL_SAFE_NO_PAD] {
+        let json = format!(r#"{{"quote": "{}", "runtimeData": {{"data": "e30", "dataType": "JSON"}}}}"#, engine.encode(&bytes));
+        assert_eq!(parse_maa_response(&json).unwrap(), expected);
+    }
+}
+
+#[test]
+fn rejects_payloads_without_a_base64url_quote() {
+    let err = parse_maa_response(r#"{"runtimeData": {}}"#).unwrap_err();
+    assert!(matches!(err, ParseError::InvalidJsonEnvelope), "{err}");
+    let standard = base64::engine::general_purpose::STANDARD.encode([0xfb, 0xff]);
+    let err = parse_maa_response(&format!(r#"{{"quote": "{standard}"}}"#)).unwrap_err();
+    assert!(matches!(err, ParseError::Base64(_)), "{err}");
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use tdx_quote_parser::layout::{HEADER_LEN, SGX_REPORT_LEN};
+use tdx_quote_parser::{parse_quote, parse_sgx_quote_v3, Quote, QuoteBodyKind, SgxQuoteV3};
+
+use common::{quote_bytes, read};
+
+fn roundtrip(path: &str) {
+    let bytes = read(path);
+    let quote = parse_quote(&bytes).unwrap();
+
+    let encoded = bincode::serialize(&quote).unwrap();
+    let decoded: Quote = bincode::deserialize(&encoded).unwrap();
+    assert_eq!(decoded, quote);
+    assert_eq!(decoded.signed_data(), quote.signed_data());
+    // Byte fields are written raw rather than as hex strings.
+    assert!(encoded.len() < 2 * quote.encoded_len());
+}
+
+#[test]
+fn bincode_roundtrip_v4() {
+    roundtrip("quote.dat");
+}
+
+#[test]
+fn bincode_roundtrip_v5() {
+    roundtrip("tests/fixtures/quote_v5.dat");
+}
+
+#[test]
+fn deserialized_body_is_what_signed_data_covers() {
+    let bytes = quote_bytes();
+    let quote = parse_quote(&bytes).unwrap();
+    let mut forged = quote.clone();
+    if let QuoteBodyKind::Tdx(body) = &mut forged.body.td_quote_body {
+        body.mrtd = [0xaa; 48];
+    }
+
+    let decoded: Quote = bincode::deserialize(&bincode::serialize(&forged).unwrap()).unwrap();
+    assert_ne!(decoded.signed_data(), quote.signed_data());
+    assert_eq!(decoded.signed_data(), &decoded.to_bytes()[..quote.signed_data().len()]);
+}
+
+#[test]
+fn bincode_roundtrip_v3_rebuilds_signed_data() {
+    let bytes = read("tests/fixtures/quote_v3.dat");
+    let quote = parse_sgx_quote_v3(&bytes).unwrap();
+    let decoded: SgxQuoteV3 = bincode::deserialize(&bincode::serialize(&quote).unwrap()).unwrap();
+    assert_eq!(decoded, quote);
+    assert_eq!(decoded.signed_data(), &bytes[..HEADER_LEN + SGX_REPORT_LEN]);
+}
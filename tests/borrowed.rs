@@ -0,0 +1,48 @@
+mod common;
+
+use tdx_quote_parser::{parse_quote, ParseError, QuoteBodyKindRef, QuoteRef};
+
+use common::read;
+
+#[test]
+fn borrowed_fields_match_the_owned_quote() {
+    for path in ["quote.dat", "tests/fixtures/quote_v5.dat"] {
+        let bytes = read(path);
+        let owned = parse_quote(&bytes).unwrap();
+        let borrowed = QuoteRef::parse(&bytes).unwrap();
+
+        assert_eq!(borrowed.header, owned.header);
+        assert_eq!((borrowed.td_quote_body_type, borrowed.size), (owned.body.td_quote_body_type, owned.body.size));
+        assert_eq!(borrowed.signature_data_len, owned.signature.signature_data_len);
+        assert_eq!(borrowed.signature, owned.signature.signature);
+        assert_eq!(borrowed.attestation_key, owned.signature.attestation_key);
+        assert_eq!(borrowed.signed_data(), owned.signed_data());
+
+        let QuoteBodyKindRef::Tdx(body) = &borrowed.td_quote_body else { panic!("{path}: not a TD body") };
+        let owned_body = owned.body.td_quote_body.as_tdx().unwrap();
+        assert_eq!(body.mrtd, &owned_body.mrtd);
+        assert_eq!(body.reportdata, &owned_body.reportdata);
+        assert!(body.tee_tcb_svn_2.is_none());
+        // The fields point into the input rather than at copies.
+        assert!(bytes.as_ptr_range().contains(&body.mrtd.as_ptr()));
+    }
+}
+
+#[test]
+fn rejects_quotes_cut_before_the_attestation_key() {
+    let bytes = read("quote.dat");
+    let err = QuoteRef::parse(&bytes[..48 + 584 + 4 + 64]).unwrap_err();
+    assert!(matches!(err, ParseError::UnexpectedEof { offset: 700, field: "attestation_key" }));
+    // The certification data is not needed.
+    QuoteRef::parse(&bytes[..48 + 584 + 4 + 128]).unwrap();
+}
+
+#[test]
+fn signature_data_len_bounds_the_signature_as_for_the_owned_parser() {
+    let mut bytes = read("quote.dat");
+    // Room for the signature but not the attestation key.
+    bytes[48 + 584..48 + 584 + 4].copy_from_slice(&100u32.to_le_bytes());
+    for err in [QuoteRef::parse(&bytes).unwrap_err(), parse_quote(&bytes).unwrap_err()] {
+        assert!(matches!(err, ParseError::UnexpectedEof { offset: 700, field: "attestation_key" }), "{err:?}");
+    }
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use tdx_quote_parser::{parse_quote, CertificationData, CertificationDataKind, ParseError, PckId};
+
+use common::quote_bytes;
+
+fn cert_data(cert_data_type: u16, data: Vec<u8>) -> CertificationData {
+    CertificationData { cert_data_type, size: data.len() as u32, data }
+}
+
+#[test]
+fn decodes_the_pem_chain_of_quote_dat() {
+    let bytes = quote_bytes();
+    let certification_data = parse_quote(&bytes).unwrap().certification_data().clone();
+
+    let CertificationDataKind::PckCertChain(chain) = certification_data.decode().unwrap() else {
+        panic!("quote.dat carries a PCK certificate chain");
+    };
+    assert_eq!(chain.len(), 3);
+    // Each certificate is a DER SEQUENCE with a two-byte length.
+    assert!(chain.iter().all(|der| der[..2] == [0x30, 0x82]));
+    assert_eq!(Some(chain), certification_data.pck_cert_chain().transpose().unwrap());
+}
+
+#[test]
+fn decodes_ppid_types() {
+    let mut data = vec![0xaa; 16];
+    data.extend_from_slice(&[0x11; 16]);
+    data.extend_from_slice(&13u16.to_le_bytes());
+    data.extend_from_slice(&[0x01, 0x00]);
+    let expected = PckId { ppid: vec![0xaa; 16], cpusvn: [0x11; 16], pcesvn: 13, pceid: [0x01, 0x00] };
+    assert_eq!(cert_data(1, data).decode().unwrap(), CertificationDataKind::PpidCleartext(expected));
+
+    // An encrypted PPID is 256 bytes, so 16 leave the rest of the data short.
+    let err = cert_data(2, vec![0; 16 + 16 + 4]).decode().unwrap_err();
+    assert!(matches!(err, ParseError::UnexpectedEof { field: "ppid", .. }), "{err}");
+}
+
+#[test]
+fn keeps_leaf_certs_and_rejects_unknown_types() {
+    assert_eq!(cert_data(4, vec![0x30, 0x00]).decode().unwrap(), CertificationDataKind::PckLeafCert(vec![0x30, 0x00]));
+    assert!(cert_data(4, vec![]).pck_cert_chain().is_none());
+
+    let err = cert_data(7, vec![]).decode().unwrap_err();
+    assert!(matches!(err, ParseError::UnsupportedCertificationDataType(7)), "{err}");
+    let err = cert_data(5, b"no certificates".to_vec()).decode().unwrap_err();
+    assert!(matches!(err, ParseError::InvalidCertChain), "{err}");
+}
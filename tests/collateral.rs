@@ -0,0 +1,253 @@
+//! The quote and collateral in `tests/fixtures/test_pki` come from a test PKI
+//! with a 100-year validity, standing in for Intel PCS.
+
+mod common;
+
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature, SigningKey};
+use tdx_quote_parser::collateral::{QeIdentity, TcbInfo, TcbStatus};
+use tdx_quote_parser::layout::{HEADER_LEN, TD_MRTD_OFFSET};
+use tdx_quote_parser::{
+    parse_quote, verify_quote, verify_quote_with_root_ca, verify_stream_with_root_ca, Collateral, Quote, VerifiedQuote,
+    VerifyError,
+};
+
+use common::read;
+
+fn fixture(name: &str) -> Vec<u8> {
+    read(&format!("tests/fixtures/test_pki/{}", name))
+}
+
+fn quote() -> Quote {
+    parse_quote(&fixture("quote.dat")).unwrap()
+}
+
+fn tcb_info() -> TcbInfo {
+    TcbInfo::from_json(&String::from_utf8(fixture("tcb_info.json")).unwrap()).unwrap()
+}
+
+fn qe_identity() -> QeIdentity {
+    QeIdentity::from_json(&String::from_utf8(fixture("qe_identity.json")).unwrap()).unwrap()
+}
+
+#[test]
+fn tcb_status_is_the_first_level_met() {
+    assert_eq!(quote().tcb_status(&tcb_info()).unwrap(), TcbStatus::SwHardeningNeeded);
+}
+
+#[test]
+fn tcb_status_rejects_other_platforms() {
+    let mut other_fmspc = tcb_info();
+    other_fmspc.fmspc[0] ^= 1;
+    assert!(matches!(quote().tcb_status(&other_fmspc), Err(VerifyError::FmspcMismatch)));
+
+    let mut other_pce = tcb_info();
+    other_pce.pce_id = [1, 0];
+    assert!(matches!(quote().tcb_status(&other_pce), Err(VerifyError::TcbInfoMismatch)));
+}
+
+#[test]
+fn tcb_status_rejects_sgx_tcb_info_for_td_quotes() {
+    let mut sgx = tcb_info();
+    sgx.id = Some("SGX".into());
+    assert!(matches!(quote().tcb_status(&sgx), Err(VerifyError::TcbInfoMismatch)));
+    sgx.id = None;
+    assert!(matches!(quote().tcb_status(&sgx), Err(VerifyError::TcbInfoMismatch)));
+
+    // Levels without TDX components never match a TD quote.
+    let mut no_tdx_components = tcb_info();
+    for level in &mut no_tdx_components.tcb_levels {
+        level.tcb.tdxtcbcomponents.clear();
+    }
+    assert!(matches!(quote().tcb_status(&no_tdx_components), Err(VerifyError::TcbLevelNotFound)));
+}
+
+#[test]
+fn tcb_status_takes_the_worse_tdx_module_status() {
+    // The test quote has TDX module 1 at SVN 4, which the UpToDate platform
+    // level would otherwise accept.
+    let mut up_to_date = tcb_info();
+    up_to_date.tcb_levels[0].tcb.sgxtcbcomponents[0].svn = 2;
+    assert_eq!(quote().tcb_status(&up_to_date).unwrap(), TcbStatus::UpToDate);
+
+    let mut outdated_module = up_to_date.clone();
+    outdated_module.tdx_module_identities[0].tcb_levels[0].tcb.isvsvn = 5;
+    assert_eq!(quote().tcb_status(&outdated_module).unwrap(), TcbStatus::OutOfDate);
+
+    outdated_module.tdx_module_identities[0].tcb_levels.remove(1);
+    assert!(matches!(quote().tcb_status(&outdated_module), Err(VerifyError::TcbLevelNotFound)));
+}
+
+#[test]
+fn tcb_status_rejects_other_tdx_modules() {
+    let mut other_signer = tcb_info();
+    other_signer.tdx_module_identities[0].mrsigner[0] ^= 1;
+    assert!(matches!(quote().tcb_status(&other_signer), Err(VerifyError::TdxModuleMismatch)));
+
+    let mut other_attributes = tcb_info();
+    other_attributes.tdx_module_identities[0].attributes[0] = 1;
+    assert!(matches!(quote().tcb_status(&other_attributes), Err(VerifyError::TdxModuleMismatch)));
+    other_attributes.tdx_module_identities[0].attributes_mask[0] = 0;
+    quote().tcb_status(&other_attributes).unwrap();
+
+    let mut other_version = tcb_info();
+    other_version.tdx_module_identities[0].id = "TDX_03".into();
+    assert!(matches!(quote().tcb_status(&other_version), Err(VerifyError::TdxModuleMismatch)));
+}
+
+#[test]
+fn qe_identity_status_is_the_first_level_met() {
+    assert_eq!(quote().qe_identity_status(&qe_identity()).unwrap(), TcbStatus::SwHardeningNeeded);
+}
+
+#[test]
+fn qe_identity_status_rejects_other_enclaves() {
+    let mut sgx_qe = qe_identity();
+    sgx_qe.id = "QE".into();
+    assert!(matches!(quote().qe_identity_status(&sgx_qe), Err(VerifyError::QeIdentityMismatch)));
+
+    let mut other_signer = qe_identity();
+    other_signer.mrsigner[0] ^= 1;
+    assert!(matches!(quote().qe_identity_status(&other_signer), Err(VerifyError::QeIdentityMismatch)));
+}
+
+fn collateral() -> Collateral {
+    Collateral {
+        tcb_info_json: String::from_utf8(fixture("tcb_info.json")).unwrap(),
+        qe_identity_json: String::from_utf8(fixture("qe_identity.json")).unwrap(),
+        tcb_signing_chain: fixture("tcb_signing_chain.pem"),
+        pck_crl: fixture("pck_crl.pem"),
+        root_crl: fixture("root_crl.der"),
+    }
+}
+
+/// The collateral response `name` with its `field` object changed by `edit`
+/// and signed again with the test TCB signing key.
+fn resigned(name: &str, field: &str, edit: impl FnOnce(&mut serde_json::Value)) -> String {
+    let mut response: serde_json::Value = serde_json::from_slice(&fixture(name)).unwrap();
+    edit(&mut response[field]);
+    let body = response[field].to_string();
+    let key = hex::decode(String::from_utf8(fixture("tcb_signing_key.hex")).unwrap().trim()).unwrap();
+    let signature: Signature = SigningKey::from_slice(&key).unwrap().sign(body.as_bytes());
+    format!(r#"{{"{}":{},"signature":"{}"}}"#, field, body, hex::encode(signature.to_bytes()))
+}
+
+#[test]
+fn check_revocation_accepts_unlisted_chain() {
+    quote().check_revocation(&fixture("pck_crl.pem"), &fixture("root_crl.der")).unwrap();
+}
+
+#[test]
+fn check_revocation_rejects_revoked_certificates() {
+    let quote = quote();
+    let revoked_leaf = quote.check_revocation(&fixture("pck_crl_revoked.pem"), &fixture("root_crl.der"));
+    assert!(matches!(revoked_leaf, Err(VerifyError::CertificateRevoked)));
+    let revoked_intermediate = quote.check_revocation(&fixture("pck_crl.pem"), &fixture("root_crl_revoked.der"));
+    assert!(matches!(revoked_intermediate, Err(VerifyError::CertificateRevoked)));
+}
+
+#[test]
+fn check_revocation_rejects_crls_from_other_issuers() {
+    let quote = quote();
+    let swapped = quote.check_revocation(&fixture("root_crl.der"), &fixture("pck_crl.pem"));
+    assert!(matches!(swapped, Err(VerifyError::CrlSignatureMismatch)));
+    assert!(matches!(quote.check_revocation(b"not a CRL", &fixture("root_crl.der")), Err(VerifyError::InvalidCrl)));
+}
+
+#[test]
+fn verify_quote_accepts_the_test_quote() {
+    let verified = verify_quote_with_root_ca(&fixture("quote.dat"), &collateral(), &fixture("root_ca.der")).unwrap();
+    assert_eq!(verified.tcb_status(), TcbStatus::SwHardeningNeeded);
+    assert_eq!(verified.qe_tcb_status(), TcbStatus::SwHardeningNeeded);
+    assert_eq!(verified.quote(), &quote());
+}
+
+#[test]
+fn verify_quote_rejects_tampered_input() {
+    let root_ca = fixture("root_ca.der");
+    // The test chain does not lead to the Intel root.
+    assert!(matches!(verify_quote(&fixture("quote.dat"), &collateral()), Err(VerifyError::CertChainSignatureMismatch)));
+
+    let mut revoked = collateral();
+    revoked.pck_crl = fixture("pck_crl_revoked.pem");
+    let result = verify_quote_with_root_ca(&fixture("quote.dat"), &revoked, &root_ca);
+    assert!(matches!(result, Err(VerifyError::CertificateRevoked)));
+
+    let result = verify_quote_with_root_ca(&tampered_quote(), &collateral(), &root_ca);
+    assert!(matches!(result, Err(VerifyError::InvalidQuoteSignature)));
+
+    let mut out_of_date = collateral();
+    out_of_date.qe_identity_json = resigned("qe_identity.json", "enclaveIdentity", |identity| {
+        identity["tcbLevels"].as_array_mut().unwrap().drain(..2);
+    });
+    let result = verify_quote_with_root_ca(&fixture("quote.dat"), &out_of_date, &root_ca);
+    assert!(matches!(result, Err(VerifyError::TcbStatusRejected(TcbStatus::OutOfDate))));
+
+    let mut sgx = collateral();
+    sgx.tcb_info_json = resigned("tcb_info.json", "tcbInfo", |tcb_info| tcb_info["id"] = "SGX".into());
+    let result = verify_quote_with_root_ca(&fixture("quote.dat"), &sgx, &root_ca);
+    assert!(matches!(result, Err(VerifyError::TcbInfoMismatch)));
+}
+
+#[test]
+fn verify_quote_rejects_untrusted_collateral() {
+    let root_ca = fixture("root_ca.der");
+    let verify = |collateral: &Collateral| verify_quote_with_root_ca(&fixture("quote.dat"), collateral, &root_ca);
+    // A response signed again without changes is still accepted.
+    let mut resigned_unchanged = collateral();
+    resigned_unchanged.tcb_info_json = resigned("tcb_info.json", "tcbInfo", |_| {});
+    verify(&resigned_unchanged).unwrap();
+
+    let mut tampered = collateral();
+    tampered.tcb_info_json = tampered.tcb_info_json.replacen("\"OutOfDate\"", "\"UpToDate\"", 1);
+    assert!(matches!(verify(&tampered), Err(VerifyError::InvalidCollateralSignature)));
+
+    let mut tampered = collateral();
+    tampered.qe_identity_json = tampered.qe_identity_json.replacen("\"isvsvn\":6", "\"isvsvn\":5", 1);
+    assert!(matches!(verify(&tampered), Err(VerifyError::InvalidCollateralSignature)));
+
+    let mut expired = collateral();
+    expired.qe_identity_json = resigned("qe_identity.json", "enclaveIdentity", |identity| {
+        identity["nextUpdate"] = "2026-02-01T00:00:00Z".into();
+    });
+    assert!(matches!(verify(&expired), Err(VerifyError::CollateralExpired)));
+
+    // The PCK leaf is not issued by the root CA.
+    let mut other_signer = collateral();
+    other_signer.tcb_signing_chain = quote().certification_data().data.clone();
+    assert!(matches!(verify(&other_signer), Err(VerifyError::CertChainSignatureMismatch)));
+
+    let mut malformed = collateral();
+    malformed.tcb_info_json = "{}".into();
+    assert!(matches!(verify(&malformed), Err(VerifyError::InvalidCollateral(_))));
+}
+
+/// The test quote with its MRTD changed after signing.
+fn tampered_quote() -> Vec<u8> {
+    let mut bytes = fixture("quote.dat");
+    bytes[HEADER_LEN + TD_MRTD_OFFSET] ^= 1;
+    bytes
+}
+
+#[test]
+fn verify_stream_matches_verify_quote() {
+    let collateral = collateral();
+    let root_ca = fixture("root_ca.der");
+    let quotes = vec![fixture("quote.dat"), tampered_quote(), fixture("quote.dat"), vec![0; 16]];
+    let verdicts: Vec<_> = verify_stream_with_root_ca(quotes.clone().into_iter(), &collateral, &root_ca).collect();
+    assert_eq!(verdicts.len(), quotes.len());
+    let summary = |verdict: Result<VerifiedQuote, VerifyError>| verdict.map(|verified| verified.tcb_status()).map_err(|err| err.to_string());
+    for (quote, verdict) in quotes.iter().zip(verdicts) {
+        assert_eq!(summary(verdict), summary(verify_quote_with_root_ca(quote, &collateral, &root_ca)));
+    }
+
+    let mut revoked = collateral.clone();
+    revoked.root_crl = fixture("root_crl_revoked.der");
+    let verdicts: Vec<_> = verify_stream_with_root_ca(quotes.clone().into_iter().take(2), &revoked, &root_ca).collect();
+    assert!(verdicts.iter().all(|verdict| matches!(verdict, Err(VerifyError::CertificateRevoked))));
+
+    let mut tampered = collateral;
+    tampered.tcb_info_json = tampered.tcb_info_json.replacen("\"OutOfDate\"", "\"UpToDate\"", 1);
+    let verdicts: Vec<_> = verify_stream_with_root_ca(quotes.into_iter().take(1), &tampered, &root_ca).collect();
+    assert!(matches!(verdicts[..], [Err(VerifyError::InvalidCollateralSignature)]));
+}
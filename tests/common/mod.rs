@@ -0,0 +1,25 @@
+//! Fixture loaders shared by the integration tests.
+
+#![allow(dead_code)]
+
+use tdx_quote_parser::{parse_quote, Quote};
+
+/// Reads `path`, relative to the crate root.
+pub fn read(path: &str) -> Vec<u8> {
+    std::fs::read(format!("{}/{}", env!("CARGO_MANIFEST_DIR"), path)).unwrap()
+}
+
+/// Reads and parses the quote at `path`, relative to the crate root.
+pub fn read_quote(path: &str) -> Quote {
+    parse_quote(&read(path)).unwrap()
+}
+
+/// The bytes of `quote.dat`, a genuine v4 quote followed by padding.
+pub fn quote_bytes() -> Vec<u8> {
+    read("quote.dat")
+}
+
+/// `quote.dat`, parsed.
+pub fn quote() -> Quote {
+    read_quote("quote.dat")
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use tdx_quote_parser::{FieldDiff, Quote, QuoteBodyKind, TDQuoteBody};
+
+use common::read_quote;
+
+fn td_mut(quote: &mut Quote) -> &mut TDQuoteBody {
+    match &mut quote.body.td_quote_body {
+        QuoteBodyKind::Tdx(body) => body,
+        QuoteBodyKind::Sgx(_) => panic!("not a TD quote"),
+    }
+}
+
+#[test]
+fn diff_lists_changed_fields_in_layout_order() {
+    let left = read_quote("quote.dat");
+    assert!(left.diff(&left.clone()).is_empty());
+
+    let mut right = left.clone();
+    right.header.user_data[0] ^= 1;
+    td_mut(&mut right).reportdata = [0; 64];
+    td_mut(&mut right).mrtd[47] ^= 0xff;
+    // The signature section is not compared.
+    right.signature.signature.clear();
+
+    let fields: Vec<_> = left.diff(&right).iter().map(|diff| diff.field).collect();
+    assert_eq!(fields, ["user_data", "mrtd", "reportdata"]);
+    let mrtd = &left.diff(&right)[1];
+    assert_eq!(mrtd.left, hex::encode(left.body.td_quote_body.as_tdx().unwrap().mrtd));
+    assert_eq!(mrtd.right, hex::encode(td_mut(&mut right).mrtd));
+}
+
+#[test]
+fn diff_compares_v4_and_v5_containers() {
+    let v4 = read_quote("quote.dat");
+    let v5 = read_quote("tests/fixtures/quote_v5.dat");
+    assert_eq!(v4.diff(&v5), [FieldDiff { field: "version", left: "4".into(), right: "5".into() }]);
+}
+
+#[test]
+fn rtmr_diff_finds_the_first_diverging_rtmr() {
+    let left = read_quote("quote.dat");
+    assert_eq!(left.rtmr_diff(&read_quote("tests/fixtures/quote_v5.dat")), None);
+
+    let mut right = left.clone();
+    td_mut(&mut right).rtmr3[0] = 1;
+    assert_eq!(left.rtmr_diff(&right), Some(3));
+    td_mut(&mut right).rtmr1[0] ^= 1;
+    assert_eq!(left.rtmr_diff(&right), Some(1));
+    assert_eq!(right.rtmr_diff(&left), Some(1));
+
+    // An SGX quote has no RTMRs to match.
+    let mut sgx = left.clone();
+    sgx.body.td_quote_body = QuoteBodyKind::Sgx(left.signature.qe_report.clone());
+    assert_eq!(left.rtmr_diff(&sgx), Some(0));
+    assert_eq!(sgx.rtmr_diff(&sgx.clone()), None);
+}
@@ -0,0 +1,25 @@
+mod common;
+
+use base64::Engine;
+use tdx_quote_parser::{parse_quote, ParseError, Quote};
+
+use common::quote_bytes;
+
+#[test]
+fn from_base64_ignores_line_wrapping() {
+    let bytes = quote_bytes();
+    let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+    let wrapped: Vec<&str> = encoded.as_bytes().chunks(76).map(|line| std::str::from_utf8(line).unwrap()).collect();
+    let wrapped = format!("  {}\r\n", wrapped.join("\n"));
+
+    let expected = parse_quote(&bytes).unwrap();
+    assert_eq!(Quote::from_base64(&encoded).unwrap(), expected);
+    assert_eq!(Quote::from_base64(&wrapped).unwrap(), expected);
+}
+
+#[test]
+fn from_base64_rejects_other_alphabets() {
+    // base64url replaces `+` and `/`, which standard base64 does not accept.
+    let err = Quote::from_base64("BAACAIEAAAA-_w==").unwrap_err();
+    assert!(matches!(err, ParseError::Base64(_)), "{err}");
+}
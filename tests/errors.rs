@@ -0,0 +1,18 @@
+use base64::Engine;
+use tdx_quote_parser::ParseError;
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, ParseError> {
+    Ok(hex::decode(s)?)
+}
+
+fn decode_base64(s: &str) -> Result<Vec<u8>, ParseError> {
+    Ok(base64::engine::general_purpose::STANDARD.decode(s)?)
+}
+
+#[test]
+fn decoder_errors_convert_with_question_mark() {
+    assert!(matches!(decode_hex("0g"), Err(ParseError::Hex(hex::FromHexError::InvalidHexCharacter { .. }))));
+    assert!(matches!(decode_base64("!!!!"), Err(ParseError::Base64(_))));
+    let err = decode_hex("abc").unwrap_err();
+    assert!(std::error::Error::source(&err).is_some());
+}
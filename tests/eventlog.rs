@@ -0,0 +1,47 @@
+//! `fixtures/ccel.bin` is a synthetic CCEL log with SHA-256 and SHA-384
+//! digests: one MRTD event, two events each for RTMR0 and RTMR1, one for
+//! RTMR2, and 0xff padding after the last record.
+
+mod common;
+
+use tdx_quote_parser::eventlog::{parse_ccel, replay_rtmr};
+use tdx_quote_parser::ParseError;
+
+use common::read;
+
+fn ccel() -> Vec<u8> {
+    read("tests/fixtures/ccel.bin")
+}
+
+#[test]
+fn parses_rtmr_events() {
+    let events = parse_ccel(&ccel()).unwrap();
+    let indices: Vec<u32> = events.iter().map(|event| event.rtmr_index).collect();
+    assert_eq!(indices, [0, 1, 1, 0, 2]);
+    assert_eq!(events[1].event_type, 0x8000_0003);
+    assert_eq!(events[1].event_data, b"kernel");
+    assert_eq!(
+        hex::encode(events[1].digest),
+        "e1685b6426d9c5be8691ff8f95eacdc11753355667394aa23124570b14baf68ab322a3aaeb3c599c448ad5016c709a49"
+    );
+}
+
+#[test]
+fn replays_rtmrs() {
+    let events = parse_ccel(&ccel()).unwrap();
+    let expected = [
+        "a2d79b4859dfec3926e4babb1afbb0a4ba90a80ddc9e385c8ddef23507a81eafe766279270324573bbed0e6f3ab877c4",
+        "bc361fbf90567467bb9414eaeba85169f55c7ee668475bee8e6bc88090dca42cf319b760af2e4303f3b0d0fd01d61f2d",
+        "8a45599e5e77b24c4b6472869c24e10490c4d5df715fbd0811fc4b33d370276cf00bb24871d3a1987587d6b9283d4f42",
+    ];
+    for (index, expected) in expected.iter().enumerate() {
+        assert_eq!(hex::encode(replay_rtmr(&events, index as u32)), *expected);
+    }
+    assert_eq!(replay_rtmr(&events, 3), [0; 48]);
+}
+
+#[test]
+fn rejects_truncated_logs() {
+    let ccel = ccel();
+    assert!(matches!(parse_ccel(&ccel[..ccel.len() - 80]), Err(ParseError::UnexpectedEof { .. })));
+}
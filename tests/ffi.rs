@@ -0,0 +1,52 @@
+mod common;
+
+use std::ffi::CStr;
+use std::mem::MaybeUninit;
+use std::ptr;
+
+use tdx_quote_parser::ffi::{tdx_last_error, tdx_parse_quote, TdxQuoteView, TDX_ERR_NOT_TDX, TDX_ERR_NULL_POINTER, TDX_ERR_PARSE, TDX_OK};
+use tdx_quote_parser::{parse_quote, QuoteBodyKind, QuoteBodyType, TEEType};
+
+use common::quote_bytes;
+
+fn parse(bytes: &[u8]) -> Result<TdxQuoteView, (i32, String)> {
+    let mut view = MaybeUninit::<TdxQuoteView>::uninit();
+    let status = unsafe { tdx_parse_quote(bytes.as_ptr(), bytes.len(), view.as_mut_ptr()) };
+    if status == TDX_OK {
+        return Ok(unsafe { view.assume_init() });
+    }
+    let message = unsafe { CStr::from_ptr(tdx_last_error()) };
+    Err((status, message.to_str().unwrap().to_string()))
+}
+
+#[test]
+fn fills_the_view_from_quote_dat() {
+    let bytes = quote_bytes();
+    let quote = parse_quote(&bytes).unwrap();
+    let body = quote.body.td_quote_body.as_tdx().unwrap();
+
+    let view = parse(&bytes).unwrap();
+    assert_eq!((view.version, view.attestation_key_type, view.tee_type, view.td_quote_body_type), (4, 2, 0x81, 2));
+    assert_eq!(view.mrtd, body.mrtd);
+    assert_eq!(view.rtmr2, body.rtmr2);
+    assert_eq!(view.reportdata, body.reportdata);
+}
+
+#[test]
+fn reports_failures_through_tdx_last_error() {
+    let bytes = quote_bytes();
+    let (status, message) = parse(&bytes[..100]).unwrap_err();
+    assert_eq!(status, TDX_ERR_PARSE);
+    assert!(message.starts_with("unexpected EOF"), "{message}");
+
+    let status = unsafe { tdx_parse_quote(ptr::null(), 0, ptr::null_mut()) };
+    assert_eq!(status, TDX_ERR_NULL_POINTER);
+
+    let mut sgx = parse_quote(&bytes).unwrap();
+    sgx.header.tee_type = TEEType::SGX;
+    sgx.body.td_quote_body_type = QuoteBodyType::SgxReport;
+    sgx.body.size = 384;
+    sgx.body.td_quote_body = QuoteBodyKind::Sgx(sgx.signature.qe_report.clone());
+    let (status, _) = parse(&sgx.to_bytes()).unwrap_err();
+    assert_eq!(status, TDX_ERR_NOT_TDX);
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use std::io::Write;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use tdx_quote_parser::{gunzip_if_compressed, parse_maybe_gzip, parse_quote, ParseError, MAX_QUOTE_LEN};
+
+use common::quote_bytes;
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn compressed_and_plain_quotes_parse_alike() {
+    let bytes = quote_bytes();
+    let quote = parse_quote(&bytes).unwrap();
+    assert_eq!(parse_maybe_gzip(&gzip(&bytes)).unwrap(), quote);
+    assert_eq!(parse_maybe_gzip(&bytes).unwrap(), quote);
+}
+
+#[test]
+fn decompression_stops_at_the_limit() {
+    assert_eq!(gunzip_if_compressed(&gzip(&vec![0; MAX_QUOTE_LEN])).unwrap().len(), MAX_QUOTE_LEN);
+    let bomb = gzip(&vec![0; 16 * MAX_QUOTE_LEN]);
+    assert!(bomb.len() < MAX_QUOTE_LEN / 10);
+    assert!(matches!(gunzip_if_compressed(&bomb), Err(ParseError::DecompressedTooLarge { limit: MAX_QUOTE_LEN })));
+}
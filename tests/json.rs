@@ -0,0 +1,25 @@
+mod common;
+
+use base64::Engine;
+use tdx_quote_parser::{parse_quote, ParseError, Quote};
+
+use common::quote_bytes;
+
+#[test]
+fn from_json_envelope_accepts_hex_and_base64() {
+    let bytes = quote_bytes();
+    let expected = parse_quote(&bytes).unwrap();
+
+    let hex = format!(r#"{{"td_quote": "{}", "nonce": 1}}"#, hex::encode(&bytes));
+    assert_eq!(Quote::from_json_envelope(&hex, "td_quote").unwrap(), expected);
+    let base64 = format!(r#"{{"quote": "{}"}}"#, base64::engine::general_purpose::STANDARD.encode(&bytes));
+    assert_eq!(Quote::from_json_envelope(&base64, "quote").unwrap(), expected);
+}
+
+#[test]
+fn from_json_envelope_rejects_missing_or_non_string_fields() {
+    for json in [r#"{"quote": 4}"#, r#"{"other": "00"}"#, r#"["quote"]"#, "not json"] {
+        let err = Quote::from_json_envelope(json, "quote").unwrap_err();
+        assert!(matches!(err, ParseError::InvalidJsonEnvelope), "{json}: {err}");
+    }
+}
@@ -0,0 +1,22 @@
+use tdx_quote_parser::{parse_measurement, parse_measurement_32, parse_measurement_48, ParseError};
+
+const MRTD: &str = "f2dd2696f69b950645832bdc095ffd11247eeff687eeacdb57a58d2ddb9a9f94fea40c961e19460c00ffa31420ecbc18";
+
+#[test]
+fn decodes_trimmed_and_prefixed_hex() {
+    let expected = parse_measurement_48(MRTD).unwrap();
+    assert_eq!(expected[..2], [0xf2, 0xdd]);
+    assert_eq!(parse_measurement_48(&format!(" 0x{MRTD}\n")).unwrap(), expected);
+    assert_eq!(parse_measurement_48(&MRTD.to_uppercase()).unwrap(), expected);
+    assert_eq!(parse_measurement::<2>("0a0B").unwrap(), [0x0a, 0x0b]);
+}
+
+#[test]
+fn rejects_odd_length_wrong_size_and_non_hex() {
+    let err = parse_measurement_48(&MRTD[1..]).unwrap_err();
+    assert!(matches!(err, ParseError::Hex(hex::FromHexError::OddLength)), "{err}");
+    let err = parse_measurement_32(MRTD).unwrap_err();
+    assert!(matches!(err, ParseError::InvalidMeasurementLength { expected: 32, len: 48 }), "{err}");
+    let err = parse_measurement::<2>("0g00").unwrap_err();
+    assert!(matches!(err, ParseError::Hex(hex::FromHexError::InvalidHexCharacter { c: 'g', index: 1 })), "{err}");
+}
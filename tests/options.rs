@@ -0,0 +1,111 @@
+mod common;
+
+use tdx_quote_parser::layout::{HEADER_LEN, TD_ATTRIBUTES_OFFSET};
+use tdx_quote_parser::{parse_quote, parse_quote_with_len, parse_quote_with_options, ParseError, QuoteParser, TEEType, INTEL_QE_VENDOR_ID};
+
+use common::{quote_bytes, read};
+
+fn with_tee_type(path: &str, tee_type: u32) -> Vec<u8> {
+    let mut bytes = read(path);
+    bytes[4..8].copy_from_slice(&tee_type.to_le_bytes());
+    bytes
+}
+
+fn with_qe_vendor_id(id: [u8; 16]) -> Vec<u8> {
+    let mut bytes = quote_bytes();
+    // The ID follows the version, key type, TEE type and reserved fields.
+    bytes[12..28].copy_from_slice(&id);
+    bytes
+}
+
+fn with_tdattributes(bits: u64) -> Vec<u8> {
+    let mut bytes = quote_bytes();
+    let offset = HEADER_LEN + TD_ATTRIBUTES_OFFSET;
+    bytes[offset..offset + 8].copy_from_slice(&bits.to_le_bytes());
+    bytes
+}
+
+#[test]
+fn strict_reserved_accepts_defined_td_attributes() {
+    let parser = QuoteParser::new().strict_reserved(true);
+    // DEBUG, SEPT_VE_DISABLE, MIGRATABLE, PKS, KL and PERFMON.
+    let defined = 1 | 1 << 28 | 1 << 29 | 1 << 30 | 1 << 31 | 1 << 63;
+    let quote = parser.parse(&with_tdattributes(defined)).unwrap();
+    let decoded = quote.body.td_quote_body.as_tdx().unwrap().tdattributes_decoded();
+    assert!(decoded.migratable);
+    let expected = "TUD:\n\t   DEBUG: True\n\t   RESERVED: 0\n\tSEC:\n\t  RESERVED: 0\n\t  SEPT_VE_DISABLE: 1\n\t  PKS: 1\n\t  KL: 1\n\tOTHER:\n\t  RESERVED: 0\n\t  PERFMON: 1";
+    assert_eq!(decoded.to_string(), expected);
+}
+
+#[test]
+fn strict_reserved_rejects_reserved_td_attributes() {
+    let parser = QuoteParser::new().strict_reserved(true);
+    for bit in [1, 8, 27, 32, 62] {
+        let err = parser.parse(&with_tdattributes(1 << bit)).unwrap_err();
+        assert!(matches!(err, ParseError::NonZeroReserved { field: "tdattributes" }), "bit {bit}: {err}");
+        QuoteParser::new().parse(&with_tdattributes(1 << bit)).unwrap();
+    }
+}
+
+#[test]
+fn require_exact_len_rejects_trailing_bytes() {
+    let bytes = quote_bytes();
+    let (_, len) = parse_quote_with_len(&bytes).unwrap();
+    assert!(len < bytes.len());
+
+    let parser = QuoteParser::new().require_exact_len(true);
+    let err = parser.parse(&bytes).unwrap_err();
+    assert!(matches!(err, ParseError::TrailingData { consumed, len: total } if consumed == len && total == bytes.len()), "{err}");
+    parser.parse(&bytes[..len]).unwrap();
+    QuoteParser::new().parse(&bytes).unwrap();
+}
+
+#[test]
+fn strict_qe_vendor_rejects_other_vendors() {
+    let parser = QuoteParser::new().strict_qe_vendor(true);
+    assert!(parser.parse(&quote_bytes()).unwrap().header.is_intel_qe());
+
+    let other = with_qe_vendor_id([0xab; 16]);
+    let err = parser.parse(&other).unwrap_err();
+    assert!(matches!(err, ParseError::UnknownQeVendor(id) if id != INTEL_QE_VENDOR_ID && id.as_bytes() == &[0xab; 16]), "{err}");
+    assert!(!QuoteParser::new().parse(&other).unwrap().header.is_intel_qe());
+}
+
+#[test]
+fn unknown_tee_types_are_kept_unless_strict() {
+    // v5 quotes describe their body, so an unknown TEE type can still be parsed.
+    let v5 = with_tee_type("tests/fixtures/quote_v5.dat", 0x82);
+    assert_eq!(QuoteParser::new().parse(&v5).unwrap().header.tee_type, TEEType::Unknown(0x82));
+    let err = QuoteParser::new().strict_tee_type(true).parse(&v5).unwrap_err();
+    assert!(matches!(err, ParseError::InvalidTeeType(0x82)), "{err}");
+
+    // v4 quotes take their body layout from the TEE type.
+    let v4 = with_tee_type("quote.dat", 0x82);
+    let err = QuoteParser::new().parse(&v4).unwrap_err();
+    assert!(matches!(err, ParseError::InvalidTeeType(0x82)), "{err}");
+}
+
+#[test]
+fn builder_collects_options() {
+    let parser = QuoteParser::new().strict_qe_vendor(true).strict_tee_type(true).strict_reserved(true).require_exact_len(true);
+    let options = parser.options();
+    assert!(options.strict_qe_vendor && options.strict_tee_type && options.strict_reserved && options.strict_trailing_data);
+    assert!(!options.skip_signature);
+
+    let bytes = quote_bytes();
+    let (_, len) = parse_quote_with_len(&bytes).unwrap();
+    assert_eq!(parser.parse(&bytes[..len]).unwrap(), parse_quote_with_options(&bytes[..len], options).unwrap());
+}
+
+#[test]
+fn parse_signature_false_skips_the_signature_section() {
+    let bytes = quote_bytes();
+    let full = parse_quote(&bytes).unwrap();
+    let quote = QuoteParser::new().parse_signature(false).parse(&bytes).unwrap();
+
+    assert_eq!(quote.body, full.body);
+    assert_eq!(quote.signed_data(), full.signed_data());
+    assert_eq!(quote.signature.signature_data_len, full.signature.signature_data_len);
+    assert!(quote.signature.signature.is_empty() && quote.signature.certification_data.data.is_empty());
+    assert_eq!(quote.encoded_len(), full.encoded_len());
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use tdx_quote_parser::{parse_quote, parse_quote_with_len, AttestationKeyType, QuoteRef};
+
+use common::quote_bytes;
+
+/// quote.dat re-encoded with a P-384 attestation key and 96-byte signature.
+fn p384_quote_bytes() -> Vec<u8> {
+    let bytes = quote_bytes();
+    let mut quote = parse_quote(&bytes).unwrap();
+    quote.header.attestation_key_type = AttestationKeyType::EcdsaP384;
+    quote.signature.signature = vec![0x51; 96];
+    quote.signature.attestation_key = vec![0x4b; 96];
+    quote.signature.signature_data_len += 2 * 32;
+    quote.to_bytes()
+}
+
+#[test]
+fn parses_96_byte_signatures_and_keys() {
+    let bytes = p384_quote_bytes();
+    let (quote, len) = parse_quote_with_len(&bytes).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(quote.header.attestation_key_type, AttestationKeyType::EcdsaP384);
+    assert_eq!(quote.signature.signature, [0x51; 96]);
+    assert_eq!(quote.signature.attestation_key, [0x4b; 96]);
+    // The QE report after the key is still found.
+    assert_eq!((quote.signature.qe_report.isv_prod_id, quote.signature.qe_report.isv_svn), (2, 5));
+    assert_eq!(quote.to_bytes(), bytes);
+
+    let borrowed = QuoteRef::parse(&bytes).unwrap();
+    assert_eq!(borrowed.signature, [0x51; 96]);
+    assert_eq!(borrowed.attestation_key, [0x4b; 96]);
+}
+
+#[test]
+fn p256_lengths_do_not_parse_as_p384() {
+    let bytes = quote_bytes();
+    let mut header = bytes.clone();
+    header[2] = 3;
+    // The QE report is read 64 bytes too late, so its framing no longer holds.
+    assert!(parse_quote(&header).is_err());
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use base64::Engine;
+use tdx_quote_parser::{parse_quote, CertificationData, ParseError};
+
+use common::{quote, read};
+
+/// A type 5 chain holding just `der`.
+fn pem_chain(der: &[u8]) -> CertificationData {
+    let base64 = base64::engine::general_purpose::STANDARD.encode(der);
+    let data = format!("-----BEGIN CERTIFICATE-----\n{base64}\n-----END CERTIFICATE-----\n").into_bytes();
+    CertificationData { cert_data_type: 5, size: data.len() as u32, data }
+}
+
+#[test]
+fn reads_the_sgx_extension_of_quote_dat() {
+    let extensions = quote().certification_data().pck_extensions().unwrap().unwrap();
+    assert_eq!(extensions.fmspc, [0x90, 0xc0, 0x6f, 0x00, 0x00, 0x00]);
+    assert_eq!(extensions.pceid, [0, 0]);
+    assert_eq!(extensions.tcb, [2, 2, 2, 2, 3, 1, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(extensions.pcesvn, 13);
+    assert_eq!(extensions.cpusvn, extensions.tcb);
+}
+
+#[test]
+fn pck_extensions_needs_a_pck_chain() {
+    let root = read("tests/fixtures/test_pki/root_ca.der");
+    let err = pem_chain(&root).pck_extensions().unwrap().unwrap_err();
+    assert!(matches!(err, ParseError::InvalidPckExtension), "{err}");
+
+    let leaf = CertificationData { cert_data_type: 4, size: root.len() as u32, data: root };
+    assert!(leaf.pck_extensions().is_none());
+}
+
+#[test]
+fn fmspc_matches_the_full_extension() {
+    let quote = quote();
+    let extensions = quote.certification_data().pck_extensions().unwrap().unwrap();
+    assert_eq!(quote.fmspc().unwrap(), extensions.fmspc);
+
+    let test_pki = read("tests/fixtures/test_pki/quote.dat");
+    let test_pki = parse_quote(&test_pki).unwrap();
+    assert_eq!(test_pki.fmspc().unwrap(), test_pki.certification_data().pck_extensions().unwrap().unwrap().fmspc);
+}
+
+#[test]
+fn fmspc_rejects_quotes_without_a_pck_leaf() {
+    let mut quote = quote();
+    let root = read("tests/fixtures/test_pki/root_ca.der");
+    quote.signature.certification_data = pem_chain(&root);
+    assert!(matches!(quote.fmspc(), Err(ParseError::InvalidPckExtension)));
+
+    quote.signature.certification_data.cert_data_type = 4;
+    assert!(matches!(quote.fmspc(), Err(ParseError::UnsupportedCertificationDataType(4))));
+}
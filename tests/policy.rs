@@ -0,0 +1,54 @@
+mod common;
+
+use tdx_quote_parser::{Policy, Quote, TD_ATTRIBUTES_DEBUG};
+
+use common::quote;
+
+fn failed(quote: &Quote, policy: &Policy) -> Vec<&'static str> {
+    quote.evaluate(policy).failed().map(|check| check.name).collect()
+}
+
+#[test]
+fn empty_policy_accepts_without_checks() {
+    let result = quote().evaluate(&Policy::default());
+    assert!(result.is_accepted());
+    assert!(result.checks.is_empty());
+}
+
+#[test]
+fn accepts_a_quote_meeting_every_criterion() {
+    let quote = quote();
+    let body = quote.body.td_quote_body.as_tdx().unwrap();
+    let policy = Policy {
+        min_tee_tcb_svn: Some(body.tee_tcb_svn),
+        tdattributes_must_be_clear: TD_ATTRIBUTES_DEBUG,
+        allowed_mrsignerseam: vec![[0; 48]],
+        allowed_mrtd: vec![[1; 48], body.mrtd],
+    };
+    let result = quote.evaluate(&policy);
+    assert!(result.is_accepted(), "{:?}", result.checks);
+    assert_eq!(result.checks.len(), 4);
+}
+
+#[test]
+fn reports_each_failed_criterion() {
+    let quote = quote();
+    let body = quote.body.td_quote_body.as_tdx().unwrap();
+
+    // quote.dat has TEE TCB SVN 04.01.02 and PERFMON (bit 63) set.
+    let mut min_tee_tcb_svn = body.tee_tcb_svn;
+    min_tee_tcb_svn[1] += 1;
+    let policy = Policy {
+        min_tee_tcb_svn: Some(min_tee_tcb_svn),
+        tdattributes_must_be_clear: 1 << 63,
+        allowed_mrsignerseam: vec![[1; 48]],
+        allowed_mrtd: vec![[1; 48]],
+    };
+    assert_eq!(failed(&quote, &policy), ["tee_tcb_svn", "tdattributes", "mrsignerseam", "mrtd"]);
+
+    // A higher SVN in an earlier component does not make up for a lower one.
+    let mut min_tee_tcb_svn = [0; 16];
+    min_tee_tcb_svn[2] = 3;
+    let policy = Policy { min_tee_tcb_svn: Some(min_tee_tcb_svn), ..Policy::default() };
+    assert_eq!(failed(&quote, &policy), ["tee_tcb_svn"]);
+}
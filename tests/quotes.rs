@@ -0,0 +1,32 @@
+mod common;
+
+use tdx_quote_parser::{parse_quote_with_len, parse_quotes, ParseError};
+
+use common::read;
+
+/// The quote at the start of `path`, without the padding after it.
+fn quote_at(path: &str) -> Vec<u8> {
+    let bytes = read(path);
+    let (_, len) = parse_quote_with_len(&bytes).unwrap();
+    bytes[..len].to_vec()
+}
+
+#[test]
+fn parses_back_to_back_quotes() {
+    let v4 = quote_at("quote.dat");
+    let v5 = quote_at("tests/fixtures/quote_v5.dat");
+    let data = [v4.as_slice(), &v5, &v4].concat();
+
+    let quotes = parse_quotes(&data).unwrap();
+    assert_eq!(quotes.iter().map(|quote| quote.header.version).collect::<Vec<_>>(), [4, 5, 4]);
+    assert_eq!(quotes[0], quotes[2]);
+    assert!(parse_quotes(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn reports_eof_offsets_in_the_whole_input() {
+    let v4 = quote_at("quote.dat");
+    let data = [v4.as_slice(), &v4[..100]].concat();
+    let err = parse_quotes(&data).unwrap_err();
+    assert!(matches!(err, ParseError::UnexpectedEof { offset, .. } if offset > v4.len()), "{err}");
+}
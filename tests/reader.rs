@@ -0,0 +1,45 @@
+mod common;
+
+use std::io::{Cursor, Read};
+
+use tdx_quote_parser::{parse_quote_with_len, ParseError, Quote};
+
+use common::read;
+
+#[test]
+fn reads_one_quote_and_leaves_the_rest() {
+    for path in ["quote.dat", "tests/fixtures/quote_v5.dat"] {
+        let bytes = read(path);
+        let (expected, len) = parse_quote_with_len(&bytes).unwrap();
+        let mut input = bytes[..len].to_vec();
+        input.extend_from_slice(b"trailer");
+        let mut cursor = Cursor::new(input);
+
+        let quote = Quote::from_reader(&mut cursor).unwrap();
+        assert_eq!(quote, expected);
+        assert_eq!(cursor.position() as usize, len);
+        let mut rest = Vec::new();
+        cursor.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"trailer");
+    }
+}
+
+#[test]
+fn reports_where_a_short_stream_ends() {
+    let bytes = read("quote.dat");
+    let (_, len) = parse_quote_with_len(&bytes).unwrap();
+    let err = Quote::from_reader(&bytes[..100]).unwrap_err();
+    assert!(matches!(err, ParseError::UnexpectedEof { offset: 100, field: "td_quote_body" }), "{err}");
+    let err = Quote::from_reader(&bytes[..len - 1]).unwrap_err();
+    assert!(matches!(err, ParseError::UnexpectedEof { field: "signature_data", .. }), "{err}");
+}
+
+#[test]
+fn rejects_a_bad_version_before_reading_further() {
+    let mut bytes = read("quote.dat");
+    bytes[0] = 3;
+    let mut cursor = Cursor::new(bytes);
+    let err = Quote::from_reader(&mut cursor).unwrap_err();
+    assert!(matches!(err, ParseError::UnsupportedVersion(3)), "{err}");
+    assert_eq!(cursor.position(), 2);
+}
@@ -0,0 +1,15 @@
+mod common;
+
+use tdx_quote_parser::parse_quote;
+
+use common::quote_bytes;
+
+#[test]
+fn to_bytes_reproduces_the_parsed_quote() {
+    let bytes = quote_bytes();
+    let encoded = parse_quote(&bytes).unwrap().to_bytes();
+
+    // quote.dat is zero padded past the end of the quote.
+    assert_eq!(encoded, bytes[..encoded.len()]);
+    assert!(bytes[encoded.len()..].iter().all(|&b| b == 0));
+}
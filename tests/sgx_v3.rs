@@ -0,0 +1,46 @@
+//! `fixtures/quote_v3.dat` re-frames the signature section of `quote.dat` as
+//! an SGX DCAP v3 quote: a v3 header with QE SVN 5 and PCE SVN 13, the QE
+//! report of `quote.dat` standing in for the enclave report body, and the
+//! signature, attestation key, QE report and certification data copied
+//! over. The quote signature does not cover the new header and body.
+
+mod common;
+
+use tdx_quote_parser::{
+    is_sgx_quote_v3, parse_quote, parse_sgx_quote_v3, AttestationKeyType, ParseError, INTEL_QE_VENDOR_ID,
+};
+
+use common::read;
+
+#[test]
+fn v3_quote_fields() {
+    let bytes = read("tests/fixtures/quote_v3.dat");
+    assert!(is_sgx_quote_v3(&bytes));
+    let quote = parse_sgx_quote_v3(&bytes).unwrap();
+    let v4 = parse_quote(&read("quote.dat")).unwrap();
+
+    assert_eq!(quote.header.version, 3);
+    assert_eq!(quote.header.attestation_key_type, AttestationKeyType::EcdsaP256);
+    assert_eq!((quote.header.qe_svn, quote.header.pce_svn), (5, 13));
+    assert_eq!(quote.header.qe_vendor_id, INTEL_QE_VENDOR_ID);
+    assert_eq!(quote.body, v4.signature.qe_report);
+    assert_eq!(quote.signed_data(), &bytes[..48 + 384]);
+    assert_eq!(quote.signature_data_len as usize, bytes.len() - 48 - 384 - 4);
+    assert_eq!(quote.signature, v4.signature.signature);
+    assert_eq!(quote.qe_report, v4.signature.qe_report);
+    assert_eq!(quote.qe_auth_data, v4.signature.qe_auth_data);
+    assert_eq!(&quote.certification_data, v4.certification_data());
+}
+
+#[test]
+fn other_versions_are_not_v3() {
+    let v4 = read("quote.dat");
+    assert!(!is_sgx_quote_v3(&v4));
+    assert!(matches!(parse_sgx_quote_v3(&v4), Err(ParseError::UnsupportedVersion(4))));
+    let v5 = read("tests/fixtures/quote_v5.dat");
+    assert!(matches!(parse_sgx_quote_v3(&v5), Err(ParseError::UnsupportedVersion(5))));
+
+    let v3 = read("tests/fixtures/quote_v3.dat");
+    assert!(matches!(parse_quote(&v3), Err(ParseError::UnsupportedVersion(3))));
+    assert!(matches!(parse_sgx_quote_v3(&v3[..100]), Err(ParseError::UnexpectedEof { .. })));
+}
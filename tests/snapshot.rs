@@ -0,0 +1,176 @@
+//! Field-by-field snapshots of the bundled quotes.
+//!
+//! `quote.dat` is a genuine v4 quote from a TDX 1.0 platform.
+//! `fixtures/quote_v5.dat` is the same quote re-framed as v5: the version is
+//! set to 5 and a TDX 1.0 body descriptor (type 2, 584 bytes) is inserted
+//! after the header. Its body and signature section are byte for byte those of
+//! `quote.dat`. The signature no longer covers the modified header.
+
+mod common;
+
+use tdx_quote_parser::layout::{HEADER_LEN, TD_BODY_10_LEN, TD_BODY_15_LEN};
+use tdx_quote_parser::{
+    parse_quote, parse_quote_with_spans, AttestationKeyType, FieldSpan, ParseError, Quote, QuoteBodyType, QuoteRef,
+    TDQuoteBody, TEEType, INTEL_QE_VENDOR_ID,
+};
+
+use common::{quote_bytes, read};
+
+fn load(path: &str) -> (Quote, usize) {
+    let bytes = read(path);
+    let len = bytes.len();
+    (parse_quote(&bytes).unwrap(), len)
+}
+
+fn assert_hex(actual: &[u8], expected: &str) {
+    assert_eq!(hex::encode(actual), expected);
+}
+
+fn assert_td_body(body: &TDQuoteBody) {
+    assert_hex(&body.tee_tcb_svn, "04010200000000000000000000000000");
+    assert_hex(
+        &body.mrseam,
+        "9790d89a10210ec6968a773cee2ca05b5aa97309f36727a968527be4606fc19e6f73acce350946c9d46a9bf7a63f8430",
+    );
+    assert_eq!(body.mrsignerseam, [0; 48]);
+    assert_eq!(body.seamattributes, [0; 8]);
+    assert_hex(&body.tdattributes, "0000001000000080");
+    assert_hex(&body.xfam, "e702060000000000");
+    assert_hex(
+        &body.mrtd,
+        "f2dd2696f69b950645832bdc095ffd11247eeff687eeacdb57a58d2ddb9a9f94fea40c961e19460c00ffa31420ecbc18",
+    );
+    assert_eq!(body.mrconfigid, [0; 48]);
+    assert_eq!(body.mrowner, [0; 48]);
+    assert_eq!(body.mrownerconfig, [0; 48]);
+    assert_hex(
+        &body.rtmr0,
+        "0998204508d58dcbfebe5e11c48669f7a921ac2da744dfb7d014ecdff2acdff1c9f665fdad52aadacf296a1df9909eb2",
+    );
+    assert_hex(
+        &body.rtmr1,
+        "383d100224f1716aeb431f7cb3cf028197dbd872487f27b0f6329ab17647dc9953c7014109818634f879e6550bc60f93",
+    );
+    assert_hex(
+        &body.rtmr2,
+        "eecfc42ff4d49278bfdbb0c77e570f4490cff10a2ee1ac11fbd2c2b49fa6cfa3cf1a1cb755c72522dd8a689e9d47906a",
+    );
+    assert_eq!(body.rtmr3, [0; 48]);
+    assert_hex(
+        &body.reportdata,
+        "278e753482976c8a7351fe2113609c7350d491cdae3d449eefc202fa41b2ad68\
+         40239cc2ba084c2d594b4e6dabeae0fcbf71c96daf0d0c9ecf0e9810c0457900",
+    );
+    assert_eq!(body.tee_tcb_svn_2, None);
+    assert_eq!(body.mrservicetd, None);
+    assert!(!body.is_debug());
+}
+
+fn assert_header(quote: &Quote, version: u16) {
+    let header = &quote.header;
+    assert_eq!(header.version, version);
+    assert_eq!(header.attestation_key_type, AttestationKeyType::EcdsaP256);
+    assert_eq!(header.tee_type, TEEType::TDX);
+    assert_eq!(header.reserved1, [0; 2]);
+    assert_eq!(header.reserved2, [0; 2]);
+    assert_eq!(header.qe_vendor_id, INTEL_QE_VENDOR_ID);
+    assert_hex(&header.user_data, "28fa333a41ec7e302625d24f400f3f2400000000");
+}
+
+fn assert_signature(quote: &Quote) {
+    let signature = &quote.signature;
+    assert_eq!(signature.signature_data_len, 4300);
+    assert_hex(
+        &signature.signature,
+        "79d2386455606243552b2b6f5d04ce8b99657b8b9bf25e348b925805f5c4ae2c\
+         c1e5ccff090592bb5e55ce99be1693ba9b67cc879d6fd1b0edc9524d161b2ca9",
+    );
+    assert_hex(
+        &signature.attestation_key,
+        "7aac15abe68a4571ae7d0d0de5765ecb2b76c89890e38a66fd861e76f2608e40\
+         fdf39a81de66f69bdc7ab862b59fac83dbdc28162b3b002c55b963578aa6e33b",
+    );
+    assert_eq!(signature.qe_cert_data_type, 6);
+    assert_eq!(signature.qe_cert_data_size, 4166);
+
+    let qe_report = &signature.qe_report;
+    assert_hex(&qe_report.cpu_svn, "0202181a03ff00050000000000000000");
+    assert_eq!(qe_report.misc_select, 0);
+    assert_hex(&qe_report.attributes, "1500000000000000e700000000000000");
+    assert_hex(&qe_report.mr_enclave, "86fc4e0ec2c5ddcebac97062c0a0142a97c18a7a755147bcbc3fe17d6529781d");
+    assert_hex(&qe_report.mr_signer, "dc9e2a7c6f948f17474e34a7fc43ed030f7c1563f1babddf6340c82e0e54a8c5");
+    assert_eq!(qe_report.isv_prod_id, 2);
+    assert_eq!(qe_report.isv_svn, 5);
+    assert_hex(&qe_report.report_data[..32], "99cb765d61aa180a03b3b47f229cdaf6f637878298f7cabb4ac0d3b8cadb2a4d");
+    assert_eq!(qe_report.report_data[32..], [0; 32]);
+    assert_hex(&signature.qe_auth_data, "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+
+    let certification_data = quote.certification_data();
+    assert_eq!(certification_data.cert_data_type, 5);
+    assert_eq!(certification_data.size, 3678);
+    assert_eq!(certification_data.pck_cert_chain().unwrap().unwrap().len(), 3);
+}
+
+#[test]
+fn v4_quote_fields() {
+    let (quote, len) = load("quote.dat");
+    assert_eq!(len, 4936 + 70);
+    assert_header(&quote, 4);
+    assert_eq!(quote.body.td_quote_body_type, QuoteBodyType::Tdx10);
+    assert_eq!(quote.body.size, 584);
+    assert_td_body(quote.body.td_quote_body.as_tdx().unwrap());
+    assert_signature(&quote);
+    assert_eq!(quote.signed_data().len(), 48 + 584);
+    assert_eq!(quote.encoded_len(), 4936);
+}
+
+#[test]
+fn v5_quote_fields() {
+    let (quote, len) = load("tests/fixtures/quote_v5.dat");
+    assert_eq!(len, 4942);
+    assert_header(&quote, 5);
+    assert_eq!(quote.body.td_quote_body_type, QuoteBodyType::Tdx10);
+    assert_eq!(quote.body.size, 584);
+    assert_td_body(quote.body.td_quote_body.as_tdx().unwrap());
+    assert_signature(&quote);
+    assert_eq!(quote.signed_data().len(), 48 + 6 + 584);
+    assert_eq!(quote.encoded_len(), len);
+}
+
+#[test]
+fn standalone_td_body() {
+    let bytes = read("quote.dat");
+    let body = &bytes[HEADER_LEN..];
+    assert_td_body(&TDQuoteBody::parse(&body[..TD_BODY_10_LEN]).unwrap());
+    for (len, expected) in [(100, TD_BODY_10_LEN), (TD_BODY_10_LEN + 1, TD_BODY_15_LEN), (TD_BODY_15_LEN + 1, TD_BODY_15_LEN)] {
+        let err = TDQuoteBody::parse(&body[..len]).unwrap_err();
+        assert!(matches!(err, ParseError::BodySizeMismatch { declared, expected: e } if declared as usize == len && e == expected));
+    }
+}
+
+#[test]
+fn field_spans() {
+    let bytes = read("tests/fixtures/quote_v5.dat");
+    let (quote, spans) = parse_quote_with_spans(&bytes).unwrap();
+    let span = |name| *spans.iter().find(|span| span.name == name).unwrap();
+    assert_eq!(span("td_quote_body_type"), FieldSpan { name: "td_quote_body_type", offset: 48, len: 2 });
+    let mrtd = span("mrtd");
+    assert_eq!((mrtd.offset, mrtd.len), (48 + 6 + 136, 48));
+    assert_eq!(&bytes[mrtd.offset..][..mrtd.len], &quote.body.td_quote_body.as_tdx().unwrap().mrtd);
+    assert_eq!(span("signature_data").offset, 48 + 6 + 584 + 4);
+    assert!(spans.windows(2).all(|pair| pair[0].offset <= pair[1].offset));
+}
+
+#[test]
+fn debug_renders_bytes_as_hex() {
+    let (quote, _) = load("quote.dat");
+    let mrseam = "9790d89a10210ec6968a773cee2ca05b5aa97309f36727a968527be4606fc19e6f73acce350946c9d46a9bf7a63f8430";
+    let body = quote.body.td_quote_body.as_tdx().unwrap();
+    assert_eq!(format!("{:?}", body.mrseam()), format!("Measurement({mrseam})"));
+    assert!(format!("{:?}", quote.summary()).contains(&format!("mrseam: Some({mrseam})")));
+
+    let bytes = quote_bytes();
+    let borrowed = format!("{:?}", QuoteRef::parse(&bytes).unwrap());
+    assert!(borrowed.contains(&format!("mrseam: {mrseam}")));
+    assert!(!borrowed.contains("[151, 144"));
+}
@@ -0,0 +1,47 @@
+use tdx_quote_parser::testvec::{QuoteBuilder, SigningKey};
+use tdx_quote_parser::{parse_quote, QuoteBodyType, TDQuoteBody, VerifyError};
+
+fn key() -> SigningKey {
+    SigningKey::from_slice(&[0x42; 32]).unwrap()
+}
+
+#[test]
+fn unsigned_quote_fields() {
+    let rtmrs = [[1; 48], [2; 48], [3; 48], [4; 48]];
+    let bytes = QuoteBuilder::new().mrtd([0x11; 48]).rtmrs(rtmrs).reportdata([0x22; 64]).tdattributes([1, 0, 0, 0, 0, 0, 0, 0]).build();
+    let quote = parse_quote(&bytes).unwrap();
+    assert_eq!(quote.encoded_len(), bytes.len());
+    assert_eq!(quote.body.td_quote_body_type, QuoteBodyType::Tdx10);
+
+    let body = quote.body.td_quote_body.as_tdx().unwrap();
+    assert_eq!(body.mrtd, [0x11; 48]);
+    assert_eq!(body.rtmrs(), rtmrs);
+    assert_eq!(body.reportdata, [0x22; 64]);
+    assert!(body.is_debug());
+    assert!(quote.verify_qe_report_binding().is_ok());
+    assert!(matches!(quote.verify_signature(), Err(VerifyError::InvalidAttestationKey)));
+}
+
+#[test]
+fn signed_quote_verifies() {
+    let builder = QuoteBuilder::new().version(5).mrtd([0x33; 48]).qe_auth_data(vec![7; 32]).signing_key(key());
+    let bytes = builder.build();
+    assert_eq!(bytes, builder.build());
+
+    let quote = parse_quote(&bytes).unwrap();
+    assert_eq!(quote.header.version, 5);
+    assert_eq!(quote.signature.attestation_pubkey_sec1(), key().verifying_key().to_encoded_point(false).as_bytes());
+    quote.verify_signature().unwrap();
+    quote.verify_qe_report_binding().unwrap();
+}
+
+#[test]
+fn tdx15_body() {
+    let body = TDQuoteBody { mrservicetd: Some([0x55; 48]), ..TDQuoteBody::default() };
+    let quote = parse_quote(&QuoteBuilder::new().td_body(body).signing_key(key()).build()).unwrap();
+    assert_eq!(quote.header.version, 5);
+    assert_eq!(quote.body.td_quote_body_type, QuoteBodyType::Tdx15);
+    let body = quote.body.td_quote_body.as_tdx().unwrap();
+    assert_eq!((body.tee_tcb_svn_2, body.mrservicetd), (Some([0; 16]), Some([0x55; 48])));
+    quote.verify_signature().unwrap();
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use std::time::Duration;
+
+use tdx_quote_parser::layout::{HEADER_LEN, TD_REPORT_DATA_OFFSET};
+use tdx_quote_parser::{parse_quote, AttestationKeyType, VerifyError, INTEL_SGX_ROOT_CA};
+
+use common::{quote, quote_bytes, read};
+
+/// 2025-01-01, within the validity of every certificate in `quote.dat`.
+const NOW: Duration = Duration::from_secs(1_735_689_600);
+
+#[test]
+fn genuine_quote_verifies() {
+    let quote = quote();
+    quote.verify_signature().unwrap();
+    quote.verify_qe_report_signature().unwrap();
+    quote.verify_qe_report_binding().unwrap();
+    quote.verify_cert_chain_at(INTEL_SGX_ROOT_CA, NOW).unwrap();
+}
+
+#[test]
+fn tampered_body_fails_signature() {
+    let mut bytes = quote_bytes();
+    bytes[HEADER_LEN + TD_REPORT_DATA_OFFSET] ^= 1;
+    let quote = parse_quote(&bytes).unwrap();
+    assert!(matches!(quote.verify_signature(), Err(VerifyError::InvalidQuoteSignature)));
+    // The QE report and certificate chain are unaffected.
+    quote.verify_qe_report_signature().unwrap();
+    quote.verify_cert_chain_at(INTEL_SGX_ROOT_CA, NOW).unwrap();
+}
+
+#[test]
+fn tampered_qe_report_fails_its_checks() {
+    let mut other_svn = quote();
+    other_svn.signature.qe_report.isv_svn += 1;
+    assert!(matches!(other_svn.verify_qe_report_signature(), Err(VerifyError::InvalidQeReportSignature)));
+
+    let mut other_auth_data = quote();
+    other_auth_data.signature.qe_auth_data.push(0);
+    assert!(matches!(other_auth_data.verify_qe_report_binding(), Err(VerifyError::QeReportBindingMismatch)));
+}
+
+#[test]
+fn cert_chain_checks_validity_and_anchor() {
+    let quote = quote();
+    // 2032, after the PCK leaf expired.
+    let later = Duration::from_secs(1_956_528_000);
+    assert!(matches!(quote.verify_cert_chain_at(INTEL_SGX_ROOT_CA, later), Err(VerifyError::CertificateExpired)));
+
+    let other_root = read("tests/fixtures/test_pki/root_ca.der");
+    assert!(matches!(quote.verify_cert_chain_at(&other_root, NOW), Err(VerifyError::CertChainSignatureMismatch)));
+}
+
+#[test]
+fn p384_quotes_are_not_verified() {
+    let mut quote = quote();
+    quote.header.attestation_key_type = AttestationKeyType::EcdsaP384;
+    quote.signature.signature = vec![0; 96];
+    quote.signature.attestation_key = vec![0; 96];
+    assert!(matches!(quote.verify_signature(), Err(VerifyError::UnsupportedAttestationKeyType)));
+}